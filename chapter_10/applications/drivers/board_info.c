@@ -0,0 +1,99 @@
+// Board Information API
+
+#include "board_info.h"
+#include "tock.h"
+
+bool board_info_is_present (void) {
+  // send command number 0 to the driver
+  syscall_return_t ret = command (DRIVER_NUM_BOARD_INFO, 0, 0, 0);
+  if (ret.type == TOCK_SYSCALL_SUCCESS) {
+    return true;
+  } else {
+    return false;
+  }
+}
+
+// Share the buffer with the driver, send the command that copies
+// a name to it and take the buffer back.
+static bool board_info_get_text (unsigned int command_number, char *buffer, unsigned int size, unsigned int *len) {
+  allow_rw_return_t allow_ret = allow_readwrite (DRIVER_NUM_BOARD_INFO, 0, buffer, size);
+  if (!allow_ret.success) {
+    return false;
+  }
+  syscall_return_t ret = command (DRIVER_NUM_BOARD_INFO, command_number, 0, 0);
+  allow_readwrite (DRIVER_NUM_BOARD_INFO, 0, NULL, 0);
+  if (ret.type == TOCK_SYSCALL_SUCCESS_U32) {
+    *len = ret.data[0];
+    return true;
+  } else {
+    return false;
+  }
+}
+
+bool board_info_get_name (char *buffer, unsigned int size, unsigned int *len) {
+  // command number 1 copies the board's name
+  return board_info_get_text (1, buffer, size, len);
+}
+
+bool board_info_get_chip (char *buffer, unsigned int size, unsigned int *len) {
+  // command number 2 copies the chip's name
+  return board_info_get_text (2, buffer, size, len);
+}
+
+bool board_info_get_drivers_count (unsigned int *count) {
+  // send command number 3 to the driver
+  syscall_return_t ret = command (DRIVER_NUM_BOARD_INFO, 3, 0, 0);
+  if (ret.type == TOCK_SYSCALL_SUCCESS_U32) {
+    *count = ret.data[0];
+    return true;
+  } else {
+    return false;
+  }
+}
+
+bool board_info_get_driver (unsigned int index, unsigned int *driver) {
+  // Send command number 4 to the driver with argument 1 (r2) set
+  // to the position of the driver.
+  syscall_return_t ret = command (DRIVER_NUM_BOARD_INFO, 4, index, 0);
+  if (ret.type == TOCK_SYSCALL_SUCCESS_U32) {
+    *driver = ret.data[0];
+    return true;
+  } else {
+    return false;
+  }
+}
+
+bool board_info_get_led_matrix (unsigned int *columns, unsigned int *rows) {
+  // send command number 5 to the driver
+  syscall_return_t ret = command (DRIVER_NUM_BOARD_INFO, 5, 0, 0);
+  if (ret.type == TOCK_SYSCALL_SUCCESS_U32_U32) {
+    *columns = ret.data[0];
+    *rows = ret.data[1];
+    return true;
+  } else {
+    return false;
+  }
+}
+
+bool board_info_get_sensors_count (unsigned int *count) {
+  // send command number 6 to the driver
+  syscall_return_t ret = command (DRIVER_NUM_BOARD_INFO, 6, 0, 0);
+  if (ret.type == TOCK_SYSCALL_SUCCESS_U32) {
+    *count = ret.data[0];
+    return true;
+  } else {
+    return false;
+  }
+}
+
+bool board_info_get_sensor (unsigned int index, unsigned int *driver) {
+  // Send command number 7 to the driver with argument 1 (r2) set
+  // to the position of the sensor.
+  syscall_return_t ret = command (DRIVER_NUM_BOARD_INFO, 7, index, 0);
+  if (ret.type == TOCK_SYSCALL_SUCCESS_U32) {
+    *driver = ret.data[0];
+    return true;
+  } else {
+    return false;
+  }
+}
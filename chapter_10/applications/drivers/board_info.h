@@ -0,0 +1,45 @@
+// Board Information API
+
+// Make sure this file is included only once
+#pragma once
+
+#include "tock.h"
+
+#define DRIVER_NUM_BOARD_INFO 0xa0007
+
+// Make sure that functions are exported as C functions and not C++
+// This prevents the compiler from exporing the functions using
+// the C++ name mangling style 
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// Verifies if the driver is present.
+bool board_info_is_present (void);
+
+// Copy the board's name to buffer and store its length in len.
+// The name is truncated if the buffer is too short.
+bool board_info_get_name (char *buffer, unsigned int size, unsigned int *len);
+
+// Copy the chip's name to buffer and store its length in len.
+// The name is truncated if the buffer is too short.
+bool board_info_get_chip (char *buffer, unsigned int size, unsigned int *len);
+
+// Get the number of drivers registered by the board.
+bool board_info_get_drivers_count (unsigned int *count);
+
+// Get the number of the driver at position index.
+bool board_info_get_driver (unsigned int index, unsigned int *driver);
+
+// Get the number of columns and rows of the LED matrix.
+bool board_info_get_led_matrix (unsigned int *columns, unsigned int *rows);
+
+// Get the number of sensors.
+bool board_info_get_sensors_count (unsigned int *count);
+
+// Get the driver number of the sensor at position index.
+bool board_info_get_sensor (unsigned int index, unsigned int *driver);
+
+#ifdef __cplusplus
+}
+#endif
@@ -0,0 +1,25 @@
+// Character Tones API
+
+#include "character_tones.h"
+#include "tock.h"
+
+bool character_tones_is_present (void) {
+  // send command number 0 to the driver
+  syscall_return_t ret = command (DRIVER_NUM_CHARACTER_TONES, 0, 0, 0);
+  if (ret.type == TOCK_SYSCALL_SUCCESS) {
+    return true;
+  } else {
+    return false;
+  }
+}
+
+bool character_tones_enable (bool enable) {
+  // Send command number 1 to the driver with argument 1 (r2) set
+  // to 1 to enable the tones or 0 to disable them.
+  syscall_return_t ret = command (DRIVER_NUM_CHARACTER_TONES, 1, enable ? 1 : 0, 0);
+  if (ret.type == TOCK_SYSCALL_SUCCESS) {
+    return true;
+  } else {
+    return false;
+  }
+}
@@ -0,0 +1,25 @@
+// Character Tones API
+
+// Make sure this file is included only once
+#pragma once
+
+#include "tock.h"
+
+#define DRIVER_NUM_CHARACTER_TONES 0xa000A
+
+// Make sure that functions are exported as C functions and not C++
+// This prevents the compiler from exporing the functions using
+// the C++ name mangling style 
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// Verifies if the driver is present.
+bool character_tones_is_present (void);
+
+// Enable or disable the tone played for every displayed character.
+bool character_tones_enable (bool enable);
+
+#ifdef __cplusplus
+}
+#endif
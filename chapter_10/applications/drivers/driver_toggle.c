@@ -0,0 +1,47 @@
+// Driver Toggle API
+
+#include "driver_toggle.h"
+#include "tock.h"
+
+bool driver_toggle_is_present (void) {
+  // send command number 0 to the driver
+  syscall_return_t ret = command (DRIVER_NUM_DRIVER_TOGGLE, 0, 0, 0);
+  if (ret.type == TOCK_SYSCALL_SUCCESS) {
+    return true;
+  } else {
+    return false;
+  }
+}
+
+bool driver_toggle_disable (unsigned int driver_num) {
+  // Send command number 1 to the driver with argument 1 (r2) set 
+  // to the number of the driver to disable.
+  syscall_return_t ret = command (DRIVER_NUM_DRIVER_TOGGLE, 1, driver_num, 0);
+  if (ret.type == TOCK_SYSCALL_SUCCESS) {
+    return true;
+  } else {
+    return false;
+  }
+}
+
+bool driver_toggle_enable (unsigned int driver_num) {
+  // Send command number 2 to the driver with argument 1 (r2) set 
+  // to the number of the driver to enable.
+  syscall_return_t ret = command (DRIVER_NUM_DRIVER_TOGGLE, 2, driver_num, 0);
+  if (ret.type == TOCK_SYSCALL_SUCCESS) {
+    return true;
+  } else {
+    return false;
+  }
+}
+
+bool driver_toggle_is_enabled (unsigned int driver_num) {
+  // Send command number 3 to the driver with argument 1 (r2) set 
+  // to the number of the driver to verify.
+  syscall_return_t ret = command (DRIVER_NUM_DRIVER_TOGGLE, 3, driver_num, 0);
+  if (ret.type == TOCK_SYSCALL_SUCCESS_U32) {
+    return ret.data[0] == 1;
+  } else {
+    return false;
+  }
+}
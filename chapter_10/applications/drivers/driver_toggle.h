@@ -0,0 +1,33 @@
+// Driver Toggle API
+
+// Make sure this file is included only once
+#pragma once
+
+#include "tock.h"
+
+#define DRIVER_NUM_DRIVER_TOGGLE 0xa0004
+
+// Make sure that functions are exported as C functions and not C++
+// This prevents the compiler from exporing the functions using
+// the C++ name mangling style 
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// Verifies if the driver is present.
+// The driver is present only for the supervisor process, the
+// application that the board loads first (the first one in flash).
+bool driver_toggle_is_present (void);
+
+// Disable the driver with the number driver_num.
+bool driver_toggle_disable (unsigned int driver_num);
+
+// Enable the driver with the number driver_num.
+bool driver_toggle_enable (unsigned int driver_num);
+
+// Verifies if the driver with the number driver_num is enabled.
+bool driver_toggle_is_enabled (unsigned int driver_num);
+
+#ifdef __cplusplus
+}
+#endif
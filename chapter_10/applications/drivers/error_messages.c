@@ -0,0 +1,25 @@
+// Error Messages API
+
+#include "error_messages.h"
+#include "tock.h"
+
+bool error_messages_is_present (void) {
+  // send command number 0 to the driver
+  syscall_return_t ret = command (DRIVER_NUM_ERROR_MESSAGES, 0, 0, 0);
+  if (ret.type == TOCK_SYSCALL_SUCCESS) {
+    return true;
+  } else {
+    return false;
+  }
+}
+
+bool error_messages_report (unsigned int driver, unsigned int error) {
+  // Send command number 1 to the driver with argument 1 (r2) set
+  // to the driver number and argument 2 (r3) set to the error code.
+  syscall_return_t ret = command (DRIVER_NUM_ERROR_MESSAGES, 1, driver, error);
+  if (ret.type == TOCK_SYSCALL_SUCCESS) {
+    return true;
+  } else {
+    return false;
+  }
+}
@@ -0,0 +1,26 @@
+// Error Messages API
+
+// Make sure this file is included only once
+#pragma once
+
+#include "tock.h"
+
+#define DRIVER_NUM_ERROR_MESSAGES 0xa0006
+
+// Make sure that functions are exported as C functions and not C++
+// This prevents the compiler from exporing the functions using
+// the C++ name mangling style 
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// Verifies if the driver is present.
+bool error_messages_is_present (void);
+
+// Report an error returned by a driver, its short code is displayed
+// on the LED matrix and its description is printed on the console.
+bool error_messages_report (unsigned int driver, unsigned int error);
+
+#ifdef __cplusplus
+}
+#endif
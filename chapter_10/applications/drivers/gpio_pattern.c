@@ -0,0 +1,39 @@
+// GPIO Pattern API
+
+#include "gpio_pattern.h"
+#include "tock.h"
+
+bool gpio_pattern_is_present (unsigned int *pins) {
+  // send command number 0 to the driver
+  syscall_return_t ret = command (DRIVER_NUM_GPIO_PATTERN, 0, 0, 0);
+  if (ret.type == TOCK_SYSCALL_SUCCESS_U32) {
+    *pins = ret.data[0];
+    return true;
+  } else {
+    return false;
+  }
+}
+
+bool gpio_pattern_start (unsigned int pin, unsigned int pattern, unsigned int length, unsigned int period_ms) {
+  // Send command number 1 to the driver with argument 1 (r2) set
+  // to the pin, the length and the period and argument 2 (r3) set
+  // to the pattern.
+  unsigned int settings = (pin & 0xff) | ((length & 0xff) << 8) | ((period_ms & 0xffff) << 16);
+  syscall_return_t ret = command (DRIVER_NUM_GPIO_PATTERN, 1, settings, pattern);
+  if (ret.type == TOCK_SYSCALL_SUCCESS) {
+    return true;
+  } else {
+    return false;
+  }
+}
+
+bool gpio_pattern_stop (unsigned int pin) {
+  // Send command number 2 to the driver with argument 1 (r2) set
+  // to the pin.
+  syscall_return_t ret = command (DRIVER_NUM_GPIO_PATTERN, 2, pin, 0);
+  if (ret.type == TOCK_SYSCALL_SUCCESS) {
+    return true;
+  } else {
+    return false;
+  }
+}
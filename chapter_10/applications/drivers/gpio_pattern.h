@@ -0,0 +1,37 @@
+// GPIO Pattern API
+
+// Make sure this file is included only once
+#pragma once
+
+#include "tock.h"
+
+#define DRIVER_NUM_GPIO_PATTERN 0xa0008
+
+// Make sure that functions are exported as C functions and not C++
+// This prevents the compiler from exporing the functions using
+// the C++ name mangling style 
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// The driver has 3 pins, pattern pins 0 to 2:
+// - micro:bit: edge connector pins P13, P14 and P15
+// - Raspberry Pi Pico: none by default, GPIO 20, 21 and 22 if the kernel
+//   is built with the gpio_pattern feature, in which case they are not
+//   available through the GPIO driver
+
+// Verifies if the driver is present and returns the number of pins.
+bool gpio_pattern_is_present (unsigned int *pins);
+
+// Start playing a pattern on a pin. The pattern has length states
+// (1 to 32), starting with the most significant of the length bits
+// of pattern, 1 is on and 0 is off. Each state is played for
+// period_ms milliseconds and the pattern repeats.
+bool gpio_pattern_start (unsigned int pin, unsigned int pattern, unsigned int length, unsigned int period_ms);
+
+// Stop the pattern played on a pin and turn the pin off.
+bool gpio_pattern_stop (unsigned int pin);
+
+#ifdef __cplusplus
+}
+#endif
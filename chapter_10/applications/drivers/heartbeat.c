@@ -0,0 +1,24 @@
+// Heartbeat API
+
+#include "heartbeat.h"
+#include "tock.h"
+
+bool heartbeat_is_present (void) {
+  // send command number 0 to the driver
+  syscall_return_t ret = command (DRIVER_NUM_HEARTBEAT, 0, 0, 0);
+  if (ret.type == TOCK_SYSCALL_SUCCESS) {
+    return true;
+  } else {
+    return false;
+  }
+}
+
+bool heartbeat_beat (void) {
+  // send command number 1 to the driver
+  syscall_return_t ret = command (DRIVER_NUM_HEARTBEAT, 1, 0, 0);
+  if (ret.type == TOCK_SYSCALL_SUCCESS) {
+    return true;
+  } else {
+    return false;
+  }
+}
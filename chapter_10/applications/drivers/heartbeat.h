@@ -0,0 +1,29 @@
+// Heartbeat API
+
+// Make sure this file is included only once
+#pragma once
+
+#include "tock.h"
+
+#define DRIVER_NUM_HEARTBEAT 0xa0005
+
+// Make sure that functions are exported as C functions and not C++
+// This prevents the compiler from exporing the functions using
+// the C++ name mangling style 
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// Verifies if the driver is present.
+bool heartbeat_is_present (void);
+
+// Send a heartbeat.
+//
+// The board decides which processes have to send heartbeats and how
+// often, a process that misses its heartbeat is restarted. Fails for
+// the processes that the board does not supervise.
+bool heartbeat_beat (void);
+
+#ifdef __cplusplus
+}
+#endif
@@ -23,3 +23,238 @@ bool led_matrix_text_set_speed (unsigned int speed) {
     return false;
   }
 }
+
+bool led_matrix_text_set_next_speed (unsigned int speed) {
+  // Send command number 20 to the driver with argument 1 (r2) set
+  // to the speed in ms of the next printed message.
+  syscall_return_t ret = command (DRIVER_NUM_LED_MATRIX_TEXT, 20, speed, 0);
+  if (ret.type == TOCK_SYSCALL_SUCCESS) {
+    return true;
+  } else {
+    return false;
+  }
+}
+
+bool led_matrix_text_set_gap (unsigned int gap) {
+  // Send command number 13 to the driver with argument 1 (r2) set
+  // to the time in ms the matrix is blank between two characters.
+  syscall_return_t ret = command (DRIVER_NUM_LED_MATRIX_TEXT, 13, gap, 0);
+  if (ret.type == TOCK_SYSCALL_SUCCESS) {
+    return true;
+  } else {
+    return false;
+  }
+}
+
+bool led_matrix_text_set_blink_rate (unsigned int rate) {
+  // Send command number 14 to the driver with argument 1 (r2) set
+  // to the number of blinks of a character.
+  syscall_return_t ret = command (DRIVER_NUM_LED_MATRIX_TEXT, 14, rate, 0);
+  if (ret.type == TOCK_SYSCALL_SUCCESS) {
+    return true;
+  } else {
+    return false;
+  }
+}
+
+bool led_matrix_text_set_speed_ramp (unsigned int start_speed, unsigned int target_speed) {
+  // Send command number 17 to the driver with argument 1 (r2) set
+  // to the speed at which the ramp starts.
+  syscall_return_t ret = command (DRIVER_NUM_LED_MATRIX_TEXT, 17, start_speed, 0);
+  if (ret.type != TOCK_SYSCALL_SUCCESS) {
+    return false;
+  }
+  // Send command number 18 to the driver with argument 1 (r2) set
+  // to the speed at which the ramp ends.
+  ret = command (DRIVER_NUM_LED_MATRIX_TEXT, 18, target_speed, 0);
+  if (ret.type == TOCK_SYSCALL_SUCCESS) {
+    return true;
+  } else {
+    return false;
+  }
+}
+
+bool led_matrix_text_set_word_pause (unsigned int pause) {
+  // Send command number 19 to the driver with argument 1 (r2) set
+  // to the pause between two words.
+  syscall_return_t ret = command (DRIVER_NUM_LED_MATRIX_TEXT, 19, pause, 0);
+  if (ret.type == TOCK_SYSCALL_SUCCESS) {
+    return true;
+  } else {
+    return false;
+  }
+}
+
+bool led_matrix_text_set_attributes (unsigned int attributes) {
+  // Send command number 2 to the driver with argument 1 (r2) set
+  // to the attributes of the next printed message.
+  syscall_return_t ret = command (DRIVER_NUM_LED_MATRIX_TEXT, 2, attributes, 0);
+  if (ret.type == TOCK_SYSCALL_SUCCESS) {
+    return true;
+  } else {
+    return false;
+  }
+}
+
+bool led_matrix_text_set_mode (unsigned int mode) {
+  // Send command number 3 to the driver with argument 1 (r2) set
+  // to the display mode.
+  syscall_return_t ret = command (DRIVER_NUM_LED_MATRIX_TEXT, 3, mode, 0);
+  if (ret.type == TOCK_SYSCALL_SUCCESS) {
+    return true;
+  } else {
+    return false;
+  }
+}
+
+bool led_matrix_text_get_statistic (unsigned int statistic, unsigned int *value) {
+  // Send command number 4 to the driver with argument 1 (r2) set
+  // to the requested statistic.
+  syscall_return_t ret = command (DRIVER_NUM_LED_MATRIX_TEXT, 4, statistic, 0);
+  if (ret.type == TOCK_SYSCALL_SUCCESS_U32) {
+    *value = ret.data[0];
+    return true;
+  } else {
+    return false;
+  }
+}
+
+bool led_matrix_text_reset_statistics (void) {
+  // send command number 5 to the driver
+  syscall_return_t ret = command (DRIVER_NUM_LED_MATRIX_TEXT, 5, 0, 0);
+  if (ret.type == TOCK_SYSCALL_SUCCESS) {
+    return true;
+  } else {
+    return false;
+  }
+}
+
+bool led_matrix_text_show_hex (unsigned int value) {
+  // Send command number 6 to the driver with argument 1 (r2) set
+  // to the value to display.
+  syscall_return_t ret = command (DRIVER_NUM_LED_MATRIX_TEXT, 6, value, 0);
+  if (ret.type == TOCK_SYSCALL_SUCCESS) {
+    return true;
+  } else {
+    return false;
+  }
+}
+
+bool led_matrix_text_set_placeholder (unsigned int glyph) {
+  // Send command number 7 to the driver with argument 1 (r2) set
+  // to the glyph displayed for unknown characters.
+  syscall_return_t ret = command (DRIVER_NUM_LED_MATRIX_TEXT, 7, glyph, 0);
+  if (ret.type == TOCK_SYSCALL_SUCCESS) {
+    return true;
+  } else {
+    return false;
+  }
+}
+
+bool led_matrix_text_show_fixed_point (int value, unsigned int decimals) {
+  // Send command number 8 to the driver with argument 1 (r2) set
+  // to the value and argument 2 (r3) set to the number of decimals.
+  syscall_return_t ret = command (DRIVER_NUM_LED_MATRIX_TEXT, 8, value, decimals);
+  if (ret.type == TOCK_SYSCALL_SUCCESS) {
+    return true;
+  } else {
+    return false;
+  }
+}
+
+bool led_matrix_text_set_direction (unsigned int direction) {
+  // Send command number 9 to the driver with argument 1 (r2) set to the direction.
+  syscall_return_t ret = command (DRIVER_NUM_LED_MATRIX_TEXT, 9, direction, 0);
+  if (ret.type == TOCK_SYSCALL_SUCCESS) {
+    return true;
+  } else {
+    return false;
+  }
+}
+
+bool led_matrix_text_set_bounce (bool bounce) {
+  // Send command number 15 to the driver with argument 1 (r2) set to 1 to enable
+  // the bounce mode or 0 to disable it.
+  syscall_return_t ret = command (DRIVER_NUM_LED_MATRIX_TEXT, 15, bounce ? 1 : 0, 0);
+  if (ret.type == TOCK_SYSCALL_SUCCESS) {
+    return true;
+  } else {
+    return false;
+  }
+}
+
+bool led_matrix_text_set_repeat (unsigned int count) {
+  // Send command number 10 to the driver with argument 1 (r2) set to the count.
+  syscall_return_t ret = command (DRIVER_NUM_LED_MATRIX_TEXT, 10, count, 0);
+  if (ret.type == TOCK_SYSCALL_SUCCESS) {
+    return true;
+  } else {
+    return false;
+  }
+}
+
+bool led_matrix_text_set_done_callback (subscribe_upcall callback, void *user_data) {
+  // Register the function with the driver using the subscribe number 0
+  subscribe_return_t ret = subscribe (DRIVER_NUM_LED_MATRIX_TEXT, 0, callback, user_data);
+  return ret.success;
+}
+
+bool led_matrix_text_pause (void) {
+  // Send command number 11 to the driver
+  syscall_return_t ret = command (DRIVER_NUM_LED_MATRIX_TEXT, 11, 0, 0);
+  if (ret.type == TOCK_SYSCALL_SUCCESS) {
+    return true;
+  } else {
+    return false;
+  }
+}
+
+bool led_matrix_text_resume (void) {
+  // Send command number 12 to the driver
+  syscall_return_t ret = command (DRIVER_NUM_LED_MATRIX_TEXT, 12, 0, 0);
+  if (ret.type == TOCK_SYSCALL_SUCCESS) {
+    return true;
+  } else {
+    return false;
+  }
+}
+
+bool led_matrix_text_set_glyphs (const unsigned char *glyphs, unsigned int size) {
+  // Share the glyphs with the driver using the read only allow number 0
+  allow_ro_return_t ret = allow_readonly (DRIVER_NUM_LED_MATRIX_TEXT, 0, glyphs, size);
+  return ret.success;
+}
+
+bool led_matrix_text_unset_glyphs (void) {
+  // Share an empty buffer with the driver to unregister the glyphs
+  allow_ro_return_t ret = allow_readonly (DRIVER_NUM_LED_MATRIX_TEXT, 0, NULL, 0);
+  return ret.success;
+}
+
+bool led_matrix_text_set_frames (const uint32_t *frames, unsigned int count) {
+  // Share the frames with the driver using the read only allow number 1
+  allow_ro_return_t ret = allow_readonly (DRIVER_NUM_LED_MATRIX_TEXT, 1, frames, count * sizeof (uint32_t));
+  return ret.success;
+}
+
+bool led_matrix_text_unset_frames (void) {
+  // Share an empty buffer with the driver to unshare the frames
+  allow_ro_return_t ret = allow_readonly (DRIVER_NUM_LED_MATRIX_TEXT, 1, NULL, 0);
+  return ret.success;
+}
+
+bool led_matrix_text_play_animation (void) {
+  // Send command number 16 to the driver
+  syscall_return_t ret = command (DRIVER_NUM_LED_MATRIX_TEXT, 16, 0, 0);
+  if (ret.type == TOCK_SYSCALL_SUCCESS) {
+    return true;
+  } else {
+    return false;
+  }
+}
+
+bool led_matrix_text_set_animation_done_callback (subscribe_upcall callback, void *user_data) {
+  // Register the function with the driver using the subscribe number 1
+  subscribe_return_t ret = subscribe (DRIVER_NUM_LED_MATRIX_TEXT, 1, callback, user_data);
+  return ret.success;
+}
@@ -7,6 +7,47 @@
 
 #define DRIVER_NUM_LED_MATRIX_TEXT 0xa0003
 
+// Text attributes, they can be combined
+#define LED_MATRIX_TEXT_BLINK   (1 << 0)
+#define LED_MATRIX_TEXT_INVERSE (1 << 1)
+#define LED_MATRIX_TEXT_FLASH   (1 << 2)
+
+// Display modes
+#define LED_MATRIX_TEXT_CONTINUOUS  0
+#define LED_MATRIX_TEXT_WORDS       1
+#define LED_MATRIX_TEXT_MORSE       2
+#define LED_MATRIX_TEXT_SCROLL      3
+#define LED_MATRIX_TEXT_SCROLL_UP   4
+#define LED_MATRIX_TEXT_SCROLL_DOWN 5
+#define LED_MATRIX_TEXT_WIPE        6
+#define LED_MATRIX_TEXT_DISSOLVE    7
+
+// Display statistics
+#define LED_MATRIX_TEXT_FRAMES      0
+#define LED_MATRIX_TEXT_COMPLETED   1
+#define LED_MATRIX_TEXT_OVERWRITTEN 2
+#define LED_MATRIX_TEXT_DROPPED     3
+#define LED_MATRIX_TEXT_QUEUE_DEPTH 4
+#define LED_MATRIX_TEXT_LATENCY_US  5
+
+// Icons, strings with the control bytes that display them, for
+// instance "OK " LED_MATRIX_TEXT_ICON_CHECK
+#define LED_MATRIX_TEXT_ICON_HEART       "\x01"
+#define LED_MATRIX_TEXT_ICON_CHECK       "\x02"
+#define LED_MATRIX_TEXT_ICON_CROSS       "\x03"
+#define LED_MATRIX_TEXT_ICON_ARROW_UP    "\x04"
+#define LED_MATRIX_TEXT_ICON_ARROW_DOWN  "\x05"
+#define LED_MATRIX_TEXT_ICON_ARROW_LEFT  "\x06"
+#define LED_MATRIX_TEXT_ICON_ARROW_RIGHT "\x07"
+
+// The size of a user glyph, byte 0 is the character and bytes 1 to 4
+// are the glyph (little endian, bit 24 is the top left LED)
+#define LED_MATRIX_TEXT_GLYPH_SIZE 5
+
+// Text directions
+#define LED_MATRIX_TEXT_NORMAL   0
+#define LED_MATRIX_TEXT_REVERSED 1
+
 // Make sure that functions are exported as C functions and not C++
 // This prevents the compiler from exporing the functions using
 // the C++ name mangling style 
@@ -20,6 +61,99 @@ bool led_matrix_text_is_present (void);
 // Set the display speed in ms.
 bool led_matrix_text_set_speed (unsigned int speed);
 
+// Set the display speed in ms of the next value displayed by this process with
+// led_matrix_text_show_hex or led_matrix_text_show_fixed_point only, 0 means
+// the display speed. The text printed through the text screen driver does not
+// use it, as the kernel does not know which process prints it. Fails if another
+// process has set the speed and its message has not been printed yet.
+bool led_matrix_text_set_next_speed (unsigned int speed);
+
+// Set the time in ms the matrix is blank between two characters.
+bool led_matrix_text_set_gap (unsigned int gap);
+
+// Set the number of times a character of a blinking message blinks while it is displayed.
+// The rate is 1 to 6 and at most the time in ms a character is displayed.
+bool led_matrix_text_set_blink_rate (unsigned int rate);
+
+// Set a speed ramp, the speed moves from start_speed to target_speed during the
+// first characters of the text. A speed of 0 disables the ramp. The speed set
+// with led_matrix_text_set_next_speed takes precedence over the ramp.
+bool led_matrix_text_set_speed_ramp (unsigned int start_speed, unsigned int target_speed);
+
+// Set the pause in ms between two words in the word at a time mode,
+// 0 restores the default pause.
+bool led_matrix_text_set_word_pause (unsigned int pause);
+
+// Set the attributes of the next printed message.
+bool led_matrix_text_set_attributes (unsigned int attributes);
+
+// Set the display mode, continuous, one word at a time, Morse code, smooth scroll
+// (left, up or down) or a transition between characters (wipe or dissolve).
+bool led_matrix_text_set_mode (unsigned int mode);
+
+// Get a display statistic.
+bool led_matrix_text_get_statistic (unsigned int statistic, unsigned int *value);
+
+// Reset the display statistics.
+bool led_matrix_text_reset_statistics (void);
+
+// Display a value as 8 hexadecimal digits, replacing the displayed text.
+bool led_matrix_text_show_hex (unsigned int value);
+
+// Set the glyph displayed for unknown characters (bit 24 is the top left LED).
+bool led_matrix_text_set_placeholder (unsigned int glyph);
+
+// Display the fixed point value value / 10^decimals (at most 9 decimals),
+// for instance 235 with 1 decimal is displayed as 23.5.
+bool led_matrix_text_show_fixed_point (int value, unsigned int decimals);
+
+// Set the text direction, normal or reversed (the text is displayed starting with
+// its last character and scrolls from left to right).
+bool led_matrix_text_set_direction (unsigned int direction);
+
+// Enable or disable the bounce mode, the text is displayed to its end
+// and then back to its start instead of starting again.
+bool led_matrix_text_set_bounce (bool bounce);
+
+// Set the number of times the text is displayed, 0 means forever.
+bool led_matrix_text_set_repeat (unsigned int count);
+
+// Register a function that the driver calls when the text has been displayed
+// the set number of times, NULL unregisters it.
+bool led_matrix_text_set_done_callback (subscribe_upcall callback, void *user_data);
+
+// Pause the display, the displayed character stays on the matrix. The kernel
+// messages are still displayed and the display resumes if the process exits.
+// Fails if another process has paused the display.
+bool led_matrix_text_pause (void);
+
+// Resume the display from the character where it was paused. Only the process
+// that paused the display can resume it.
+bool led_matrix_text_resume (void);
+
+// Register glyphs for characters (for instance 0x80 - 0xff), they are
+// used instead of the font's glyphs. The buffer has to stay allocated
+// until the glyphs are unregistered.
+bool led_matrix_text_set_glyphs (const unsigned char *glyphs, unsigned int size);
+
+// Unregister the glyphs.
+bool led_matrix_text_unset_glyphs (void);
+
+// Share the frames of an animation with the driver (bit 24 is the top left LED).
+// The buffer has to stay allocated until the frames are unshared.
+bool led_matrix_text_set_frames (const uint32_t *frames, unsigned int count);
+
+// Unshare the frames of the animation.
+bool led_matrix_text_unset_frames (void);
+
+// Play the shared frames at the display speed, the animation replaces the text
+// until its last frame has been played.
+bool led_matrix_text_play_animation (void);
+
+// Register a function that the driver calls when the animation has been played,
+// NULL unregisters it.
+bool led_matrix_text_set_animation_done_callback (subscribe_upcall callback, void *user_data);
+
 #ifdef __cplusplus
 }
 #endif
@@ -0,0 +1,26 @@
+// Time64 API
+
+#include "time64.h"
+#include "tock.h"
+
+bool time64_is_present (void) {
+  // send command number 0 to the driver
+  syscall_return_t ret = command (DRIVER_NUM_TIME64, 0, 0, 0);
+  if (ret.type == TOCK_SYSCALL_SUCCESS) {
+    return true;
+  } else {
+    return false;
+  }
+}
+
+bool time64_now_us (uint64_t *us) {
+  // send command number 1 to the driver
+  syscall_return_t ret = command (DRIVER_NUM_TIME64, 1, 0, 0);
+  if (ret.type == TOCK_SYSCALL_SUCCESS_U64) {
+    // The 64 bit value is split in two registers, the lower half comes first.
+    *us = ((uint64_t)ret.data[1] << 32) | ret.data[0];
+    return true;
+  } else {
+    return false;
+  }
+}
@@ -0,0 +1,26 @@
+// Time64 API
+
+// Make sure this file is included only once
+#pragma once
+
+#include "tock.h"
+
+#define DRIVER_NUM_TIME64 0xa0009
+
+// Make sure that functions are exported as C functions and not C++
+// This prevents the compiler from exporing the functions using
+// the C++ name mangling style 
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// Verifies if the driver is present.
+bool time64_is_present (void);
+
+// Get the number of microseconds since the board started,
+// this value does not wrap around.
+bool time64_now_us (uint64_t *us);
+
+#ifdef __cplusplus
+}
+#endif
@@ -0,0 +1,163 @@
+//! Generates the glyph tables of the LED matrix font.
+//!
+//! The font is described as text art in `fonts/font5x5.txt`, see
+//! the comments at the beginning of that file for the format. The
+//! generated tables are written to `$OUT_DIR/font5x5.rs`.
+//!
+//! Each table is a `static` byte array, so that it is stored only
+//! in flash. The glyphs are packed, every glyph uses 25 bits starting
+//! with the most significant bit of a byte, the first row first.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// The file that describes the font
+const FONT_FILE: &str = "fonts/font5x5.txt";
+
+/// The number of rows and columns of a glyph
+const GLYPH_SIZE: usize = 5;
+
+/// The number of bytes written on a line of the generated source
+const BYTES_PER_LINE: usize = 10;
+
+/// A glyph read from the font file
+struct Glyph {
+    /// The character displayed by the glyph, used as a comment
+    name: String,
+    /// The rows of the glyph, each row has `GLYPH_SIZE` bits
+    rows: Vec<String>,
+}
+
+/// A table of glyphs read from the font file
+struct Table {
+    /// The name of the generated constant
+    name: String,
+    /// The documentation of the generated constant
+    docs: Vec<String>,
+    /// The glyphs of the table
+    glyphs: Vec<Glyph>,
+}
+
+/// Parses the font file
+fn parse(font: &str) -> Vec<Table> {
+    let mut tables: Vec<Table> = Vec::new();
+    let mut docs: Vec<String> = Vec::new();
+    for (index, line) in font.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+        if line.starts_with("///") {
+            docs.push(line.to_string());
+        } else if line.is_empty() || line.starts_with("//") {
+            continue;
+        } else if let Some(name) = line.strip_prefix("table ") {
+            tables.push(Table {
+                name: name.trim().to_string(),
+                docs: std::mem::take(&mut docs),
+                glyphs: Vec::new(),
+            });
+        } else if let Some(name) = line.strip_prefix("char ") {
+            let table = tables.last_mut().unwrap_or_else(|| {
+                panic!("{}:{}: glyph outside of a table", FONT_FILE, line_number)
+            });
+            table.glyphs.push(Glyph {
+                name: name.trim().to_string(),
+                rows: Vec::new(),
+            });
+        } else {
+            let glyph = tables
+                .last_mut()
+                .and_then(|table| table.glyphs.last_mut())
+                .unwrap_or_else(|| panic!("{}:{}: row outside of a glyph", FONT_FILE, line_number));
+            if glyph.rows.len() == GLYPH_SIZE {
+                panic!(
+                    "{}:{}: glyph {} has more than {} rows",
+                    FONT_FILE, line_number, glyph.name, GLYPH_SIZE
+                );
+            }
+            if line.len() != GLYPH_SIZE || !line.chars().all(|pixel| pixel == '#' || pixel == '.') {
+                panic!(
+                    "{}:{}: a row must have {} pixels, each `#` or `.`",
+                    FONT_FILE, line_number, GLYPH_SIZE
+                );
+            }
+            glyph.rows.push(line.replace('#', "1").replace('.', "0"));
+        }
+    }
+    for table in tables.iter() {
+        for glyph in table.glyphs.iter() {
+            if glyph.rows.len() != GLYPH_SIZE {
+                panic!(
+                    "{}: glyph {} from table {} has {} rows, expected {}",
+                    FONT_FILE,
+                    glyph.name,
+                    table.name,
+                    glyph.rows.len(),
+                    GLYPH_SIZE
+                );
+            }
+        }
+    }
+    tables
+}
+
+/// Generates the Rust source code for the tables
+fn generate(tables: &[Table]) -> String {
+    let mut source = format!(
+        "// Generated by build.rs from {}, do not edit.\n",
+        FONT_FILE
+    );
+    for table in tables {
+        source.push('\n');
+        for doc in table.docs.iter() {
+            source.push_str(doc);
+            source.push('\n');
+        }
+        // The glyphs in the table, used as a comment
+        let names: Vec<&str> = table
+            .glyphs
+            .iter()
+            .map(|glyph| glyph.name.as_str())
+            .collect();
+        source.push_str(&format!("// {}\n", names.join(", ")));
+        // Pack the glyphs' bits and pad the last byte with zeros.
+        let mut bits: String = table
+            .glyphs
+            .iter()
+            .map(|glyph| glyph.rows.concat())
+            .collect();
+        while bits.len() % 8 != 0 {
+            bits.push('0');
+        }
+        let bytes: Vec<String> = (0..bits.len() / 8)
+            .map(|index| {
+                let byte = u8::from_str_radix(&bits[index * 8..index * 8 + 8], 2)
+                    .expect("a row contains only 0 and 1");
+                format!("0x{:02x}", byte)
+            })
+            .collect();
+        source.push_str(&format!(
+            "static {}: [u8; {}] = [\n",
+            table.name,
+            bytes.len()
+        ));
+        for line in bytes.chunks(BYTES_PER_LINE) {
+            source.push_str(&format!("    {},\n", line.join(", ")));
+        }
+        source.push_str("];\n");
+    }
+    source
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed={}", FONT_FILE);
+
+    let font = fs::read_to_string(FONT_FILE)
+        .unwrap_or_else(|err| panic!("cannot read {}: {}", FONT_FILE, err));
+    let source = generate(&parse(&font));
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR is not set");
+    fs::write(Path::new(&out_dir).join("font5x5.rs"), source)
+        .expect("cannot write the generated font");
+}
@@ -0,0 +1,150 @@
+use core::mem;
+use kernel::grant::Grant;
+use kernel::process::{Error, ProcessId};
+use kernel::processbuffer::{ReadWriteProcessBuffer, WriteableProcessBuffer};
+use kernel::syscall::{CommandReturn, SyscallDriver};
+use kernel::ErrorCode;
+
+/// The driver number
+///
+/// As this is not one of Tock's standard drivers,
+/// its number has to be higher or equal to 0xa0000.
+///
+/// Our previous driver was 0xa0006 so we use the
+/// number available.
+pub const DRIVER_NUM: usize = 0xa0007;
+
+/// The description of the board, filled in by the board at initialization
+pub struct BoardDescriptor<'a> {
+    /// The name of the board
+    pub name: &'a str,
+    /// The name of the microcontroller
+    pub chip: &'a str,
+    /// The numbers of the drivers that the board registers
+    pub drivers: &'a [usize],
+    /// The number of columns and rows of the LED matrix
+    pub led_matrix: (usize, usize),
+    /// The numbers of the drivers that read the board's sensors
+    pub sensors: &'a [usize],
+}
+
+/// The data type that will be stored in each
+/// process' grant.
+#[derive(Default)]
+pub struct AppData {
+    /// The buffer where the driver copies the board's and chip's name
+    buffer: ReadWriteProcessBuffer,
+}
+
+/// Structure representing the driver
+pub struct BoardInfo<'a> {
+    /// The description of the board
+    descriptor: &'a BoardDescriptor<'a>,
+
+    /// The grant entrypoint
+    ///
+    /// The data type stored by the grant is `AppData` and
+    /// it does not register any upcalls.
+    grant: Grant<AppData, 0>,
+}
+
+impl<'a> BoardInfo<'a> {
+    /// Initializes a new driver structure
+    pub fn new(descriptor: &'a BoardDescriptor<'a>, grant: Grant<AppData, 0>) -> Self {
+        BoardInfo { descriptor, grant }
+    }
+
+    /// Copies a text to the process' buffer
+    ///
+    /// Returns the length of the text, the text is truncated
+    /// if the buffer is shorter.
+    fn copy_text(&self, process_id: ProcessId, text: &str) -> CommandReturn {
+        let res = self.grant.enter(process_id, |app, _| {
+            app.buffer
+                .mut_enter(|buffer| {
+                    for (byte, character) in buffer.iter().zip(text.bytes()) {
+                        byte.set(character);
+                    }
+                })
+                .map_err(|_| ErrorCode::RESERVE)
+        });
+        match res {
+            Ok(Ok(())) => CommandReturn::success_u32(text.len() as u32),
+            Ok(Err(err)) => CommandReturn::failure(err),
+            Err(err) => CommandReturn::failure(err.into()),
+        }
+    }
+
+    /// Returns the element of a list found at `index`
+    fn list_element(list: &[usize], index: usize) -> CommandReturn {
+        match list.get(index) {
+            Some(element) => CommandReturn::success_u32(*element as u32),
+            None => CommandReturn::failure(ErrorCode::INVAL),
+        }
+    }
+}
+
+/// The implementation of `SyscallDriver` makes `BoardInfo` a syscall driver
+impl<'a> SyscallDriver for BoardInfo<'a> {
+    fn allocate_grant(&self, process_id: ProcessId) -> Result<(), Error> {
+        // The kernel asked us to allocate the grant, all we have to
+        // do is to try to enter it. The kernel will do the task of
+        // allocating it for us.
+        self.grant.enter(process_id, |_, _| {})
+    }
+
+    fn command(
+        &self,
+        command_number: usize,
+        r2: usize,
+        _r3: usize,
+        process_id: ProcessId,
+    ) -> CommandReturn {
+        match command_number {
+            // Tock's convention states that all syscall drivers must return *success* or *success_...* for
+            // command number 0. This allows processes to verify if a driver is present.
+            0 => CommandReturn::success(),
+            // Copy the board's name to the allowed buffer and return its length
+            1 => self.copy_text(process_id, self.descriptor.name),
+            // Copy the chip's name to the allowed buffer and return its length
+            2 => self.copy_text(process_id, self.descriptor.chip),
+            // Return the number of drivers
+            3 => CommandReturn::success_u32(self.descriptor.drivers.len() as u32),
+            // Return the number of the driver at position r2
+            4 => Self::list_element(self.descriptor.drivers, r2),
+            // Return the number of columns and rows of the LED matrix
+            5 => CommandReturn::success_u32_u32(
+                self.descriptor.led_matrix.0 as u32,
+                self.descriptor.led_matrix.1 as u32,
+            ),
+            // Return the number of sensors
+            6 => CommandReturn::success_u32(self.descriptor.sensors.len() as u32),
+            // Return the driver number of the sensor at position r2
+            7 => Self::list_element(self.descriptor.sensors, r2),
+            // Inform the process that we do not understand the command
+            _ => CommandReturn::failure(ErrorCode::NOSUPPORT),
+        }
+    }
+
+    fn allow_readwrite(
+        &self,
+        process_id: ProcessId,
+        allow_num: usize,
+        mut buffer: ReadWriteProcessBuffer,
+    ) -> Result<ReadWriteProcessBuffer, (ReadWriteProcessBuffer, ErrorCode)> {
+        match allow_num {
+            // The buffer where the driver copies the names
+            0 => {
+                let res = self.grant.enter(process_id, |app, _| {
+                    // Store the new buffer and return the previous one.
+                    mem::swap(&mut app.buffer, &mut buffer);
+                });
+                match res {
+                    Ok(()) => Ok(buffer),
+                    Err(err) => Err((buffer, err.into())),
+                }
+            }
+            _ => Err((buffer, ErrorCode::NOSUPPORT)),
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use core::cell::Cell;
+use kernel::utilities::cells::TakeCell;
+use kernel::ErrorCode;
+
+/// A pool of `N` static buffers of `SZ` bytes each
+///
+/// Split-phase drivers take a buffer from the pool when they start an
+/// action and replace it when the action is done, instead of each
+/// storing its own static buffers.
+pub struct BufferPool<const N: usize, const SZ: usize> {
+    /// The buffers that are available
+    slots: [TakeCell<'static, [u8]>; N],
+
+    /// The start address of the pool's memory, used to verify
+    /// that the replaced buffers belong to the pool
+    start: usize,
+
+    /// The number of buffers that have been taken and not replaced
+    taken: Cell<usize>,
+}
+
+impl<const N: usize, const SZ: usize> BufferPool<N, SZ> {
+    /// Initializes a new pool using the memory of `buffers`
+    pub fn new(buffers: &'static mut [[u8; SZ]; N]) -> Self {
+        let start = buffers.as_ptr() as usize;
+        let mut buffers = buffers.iter_mut();
+        BufferPool {
+            slots: [(); N].map(|_| match buffers.next() {
+                Some(buffer) => TakeCell::new(&mut buffer[..]),
+                // There are exactly `N` buffers, so we never get here.
+                None => TakeCell::empty(),
+            }),
+            start,
+            taken: Cell::new(0),
+        }
+    }
+
+    /// Takes a buffer from the pool
+    ///
+    /// Returns `None` if all the buffers have been taken.
+    pub fn take(&self) -> Option<&'static mut [u8]> {
+        let buffer = self.slots.iter().find_map(|slot| slot.take());
+        if buffer.is_some() {
+            self.taken.set(self.taken.get() + 1);
+        }
+        buffer
+    }
+
+    /// Returns a buffer to the pool
+    ///
+    /// Returns `INVAL` and the buffer if the buffer was not taken from this pool.
+    pub fn replace(&self, buffer: &'static mut [u8]) -> Result<(), (ErrorCode, &'static mut [u8])> {
+        // Verify that the buffer was taken from this pool.
+        if buffer.len() != SZ
+            || (buffer.as_ptr() as usize) < self.start
+            || (buffer.as_ptr() as usize) >= self.start + N * SZ
+        {
+            return Err((ErrorCode::INVAL, buffer));
+        }
+        match self.slots.iter().find(|slot| slot.is_none()) {
+            Some(slot) => {
+                slot.replace(buffer);
+                self.taken.set(self.taken.get() - 1);
+                Ok(())
+            }
+            // All the buffers are in the pool, so this one cannot be ours.
+            None => Err((ErrorCode::INVAL, buffer)),
+        }
+    }
+
+    /// Returns the number of buffers that have been taken and not replaced
+    pub fn taken(&self) -> usize {
+        self.taken.get()
+    }
+
+    /// Verifies that at most `expected` buffers are taken
+    ///
+    /// Drivers call this when they are idle to detect buffers that
+    /// were never replaced. It panics in debug builds and does
+    /// nothing in release builds.
+    pub fn check_leaks(&self, expected: usize) {
+        debug_assert!(
+            self.taken.get() <= expected,
+            "{} buffers leaked from the pool",
+            self.taken.get() - expected
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use crate::led_matrix_text::CharacterClient;
+use core::cell::Cell;
+use kernel::hil::pwm::PwmPin;
+use kernel::hil::time::{Alarm, AlarmClient, ConvertTicks};
+use kernel::process::{Error, ProcessId};
+use kernel::syscall::{CommandReturn, SyscallDriver};
+use kernel::ErrorCode;
+
+/// The driver number
+///
+/// As this is not one of Tock's standard drivers,
+/// its number has to be higher or equal to 0xa0000.
+///
+/// Our previous driver was 0xa0009 so we use the
+/// number available.
+pub const DRIVER_NUM: usize = 0xa000A;
+
+/// The time in milliseconds a tone is played
+const TONE_MS: u32 = 40;
+
+/// Returns the frequency in Hz of the tone played for a character,
+/// `None` if the character is silent
+///
+/// Each class of characters has its own pitch, so that the
+/// listener can follow the text without seeing the display.
+fn frequency(character: char) -> Option<usize> {
+    match character {
+        ' ' => None,
+        'A'..='Z' => Some(1047),
+        'a'..='z' => Some(880),
+        '0'..='9' => Some(659),
+        '.' | ',' | '!' | '?' | ':' | '-' | '+' => Some(523),
+        // Unknown characters and icons
+        _ => Some(392),
+    }
+}
+
+/// Structure representing the driver
+///
+/// When enabled, the driver plays a short tone on the speaker
+/// for every character displayed by `LedMatrixText`.
+pub struct CharacterTones<'a, P: PwmPin, A: Alarm<'a>> {
+    /// The PWM pin of the speaker
+    speaker: &'a P,
+
+    /// The alarm used to stop the tones
+    alarm: &'a A,
+
+    /// Stores if the driver plays the tones
+    is_enabled: Cell<bool>,
+}
+
+impl<'a, P: PwmPin, A: Alarm<'a>> CharacterTones<'a, P, A> {
+    /// Initializes a new driver structure
+    pub fn new(speaker: &'a P, alarm: &'a A) -> Self {
+        CharacterTones {
+            speaker,
+            alarm,
+            is_enabled: Cell::new(false),
+        }
+    }
+}
+
+/// This implementation allows `CharacterTones` to be notified
+/// of the characters displayed by `LedMatrixText`.
+impl<'a, P: PwmPin, A: Alarm<'a>> CharacterClient for CharacterTones<'a, P, A> {
+    fn character_displayed(&self, character: char) {
+        if self.is_enabled.get() {
+            if let Some(frequency) = frequency(character) {
+                // The speaker may be used by the buzzer driver,
+                // in which case the tone is skipped.
+                if self
+                    .speaker
+                    .start(frequency, self.speaker.get_maximum_duty_cycle() / 2)
+                    .is_ok()
+                {
+                    self.alarm
+                        .set_alarm(self.alarm.now(), self.alarm.ticks_from_ms(TONE_MS));
+                }
+            }
+        }
+    }
+}
+
+/// This implementation allows `CharacterTones` to use an alarm.
+impl<'a, P: PwmPin, A: Alarm<'a>> AlarmClient for CharacterTones<'a, P, A> {
+    /// Called when the tone has been played long enough
+    fn alarm(&self) {
+        let _ = self.speaker.stop();
+    }
+}
+
+/// The implementation of `SyscallDriver` makes `CharacterTones` a syscall driver
+impl<'a, P: PwmPin, A: Alarm<'a>> SyscallDriver for CharacterTones<'a, P, A> {
+    fn allocate_grant(&self, _: ProcessId) -> Result<(), Error> {
+        // there is no grant used by this driver, we just ignore
+        // the function call and return success
+        Ok(())
+    }
+
+    fn command(
+        &self,
+        command_number: usize,
+        r2: usize,
+        _r3: usize,
+        _process_id: ProcessId,
+    ) -> CommandReturn {
+        match command_number {
+            // Tock's convention states that all syscall drivers must return *success* or *success_...* for
+            // command number 0. This allows processes to verify if a driver is present.
+            0 => CommandReturn::success(),
+            // Enable (r2 = 1) or disable (r2 = 0) the tones
+            1 => match r2 {
+                0 | 1 => {
+                    self.is_enabled.set(r2 == 1);
+                    CommandReturn::success()
+                }
+                _ => CommandReturn::failure(ErrorCode::INVAL),
+            },
+            // Inform the process that we do not understand the command
+            _ => CommandReturn::failure(ErrorCode::NOSUPPORT),
+        }
+    }
+
+    /* the default implementation of the *allow_...* functions is used */
+}
@@ -0,0 +1,166 @@
+use crate::process_slot::{self, Processes};
+use core::cell::Cell;
+use kernel::platform::SyscallFilter;
+use kernel::process::{Error, Process, ProcessId};
+use kernel::syscall::{CommandReturn, Syscall, SyscallDriver};
+use kernel::ErrorCode;
+
+/// The driver number
+///
+/// As this is not one of Tock's standard drivers,
+/// its number has to be higher or equal to 0xa0000.
+///
+/// Our previous driver was 0xa0003 so we use the
+/// number available.
+pub const DRIVER_NUM: usize = 0xa0004;
+
+/// The maximum number of drivers that can be toggled
+///
+/// The state of the drivers is stored as a bit mask
+/// in an *u32*, one bit for each driver.
+const MAX_DRIVERS: usize = 32;
+
+/// Structure representing the driver
+pub struct DriverToggle<'a> {
+    /// The driver numbers that the supervisor is allowed
+    /// to enable or disable
+    drivers: &'a [usize],
+
+    /// The bit mask of the disabled drivers, bit *n* is set
+    /// if `drivers[n]` is disabled.
+    disabled: Cell<u32>,
+
+    /// The kernel's processes array
+    processes: &'a Processes,
+
+    /// The slot in `processes` of the process that is allowed to use this driver
+    ///
+    /// The process name is chosen by the process itself, so any process
+    /// could claim to be the supervisor, the slot is decided by the board.
+    supervisor: usize,
+}
+
+impl<'a> DriverToggle<'a> {
+    /// Initializes a new driver structure
+    pub fn new(drivers: &'a [usize], processes: &'a Processes, supervisor: usize) -> Self {
+        if drivers.len() > MAX_DRIVERS {
+            panic!(
+                "Expecting at most {} drivers, {} supplied",
+                MAX_DRIVERS,
+                drivers.len()
+            );
+        }
+        DriverToggle {
+            drivers,
+            disabled: Cell::new(0),
+            processes,
+            supervisor,
+        }
+    }
+
+    /// Returns the position of `driver_number` in the
+    /// list of drivers that can be toggled
+    fn index_of(&self, driver_number: usize) -> Option<usize> {
+        self.drivers
+            .iter()
+            .position(|driver| *driver == driver_number)
+    }
+
+    /// Verifies if a driver is enabled
+    ///
+    /// Drivers that are not in the toggle list are always enabled.
+    fn is_enabled(&self, driver_number: usize) -> bool {
+        match self.index_of(driver_number) {
+            Some(index) => self.disabled.get() & (1 << index) == 0,
+            None => true,
+        }
+    }
+
+    /// Enables or disables a driver
+    fn set_enabled(&self, driver_number: usize, enabled: bool) -> Result<(), ErrorCode> {
+        // The toggle driver itself cannot be disabled, otherwise the
+        // supervisor would lose the ability to enable the drivers again.
+        if driver_number == DRIVER_NUM {
+            return Err(ErrorCode::INVAL);
+        }
+        match self.index_of(driver_number) {
+            Some(index) => {
+                if enabled {
+                    self.disabled.set(self.disabled.get() & !(1 << index));
+                } else {
+                    self.disabled.set(self.disabled.get() | (1 << index));
+                }
+                Ok(())
+            }
+            // The board did not allow this driver to be toggled
+            None => Err(ErrorCode::INVAL),
+        }
+    }
+}
+
+/// This implementation allows `DriverToggle` to be used by the kernel
+/// to filter the system calls before they reach the drivers.
+impl<'a> SyscallFilter for DriverToggle<'a> {
+    fn filter_syscall(&self, process: &dyn Process, syscall: &Syscall) -> Result<(), ErrorCode> {
+        // Only the system calls that are sent to a driver are filtered,
+        // *yield*, *memop* and *exit* are always allowed.
+        let driver_number = match *syscall {
+            Syscall::Subscribe { driver_number, .. }
+            | Syscall::Command { driver_number, .. }
+            | Syscall::ReadWriteAllow { driver_number, .. }
+            | Syscall::ReadOnlyAllow { driver_number, .. } => driver_number,
+            _ => return Ok(()),
+        };
+        if driver_number == DRIVER_NUM {
+            // Only the supervisor process is allowed to use this driver,
+            // for all the other processes the driver is not present.
+            if process_slot::slot_of(self.processes, process) == Some(self.supervisor) {
+                Ok(())
+            } else {
+                Err(ErrorCode::NODEVICE)
+            }
+        } else if self.is_enabled(driver_number) {
+            Ok(())
+        } else {
+            // Disabled drivers look like they are not present at all.
+            Err(ErrorCode::NODEVICE)
+        }
+    }
+}
+
+/// This implementation allows `DriverToggle` to expose a syscall API
+/// to the supervisor process.
+impl<'a> SyscallDriver for DriverToggle<'a> {
+    fn allocate_grant(&self, _process_id: ProcessId) -> Result<(), Error> {
+        // there is no grant used by this driver, we just ignore
+        // the function call and return success
+        Ok(())
+    }
+
+    fn command(
+        &self,
+        command_number: usize,
+        r2: usize,
+        _r3: usize,
+        _process_id: ProcessId,
+    ) -> CommandReturn {
+        // The syscall filter makes sure that only the supervisor
+        // process gets here.
+        match command_number {
+            // Tock's convention states that all syscall drivers must return *success* or *success_...* for
+            // command number 0. This allows processes to verify if a driver is present.
+            0 => CommandReturn::success(),
+            // Disable the driver with the number stored in *r2*
+            1 => self.set_enabled(r2, false).into(),
+            // Enable the driver with the number stored in *r2*
+            2 => self.set_enabled(r2, true).into(),
+            // Return 1 if the driver with the number stored in *r2* is enabled
+            // and 0 if it is disabled.
+            3 => CommandReturn::success_u32(self.is_enabled(r2) as u32),
+            // Inform the process that we do not understand the command
+            _ => CommandReturn::failure(ErrorCode::NOSUPPORT),
+        }
+    }
+
+    /* the default implementation of the *allow_...* functions is used */
+}
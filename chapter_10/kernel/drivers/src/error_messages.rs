@@ -0,0 +1,221 @@
+use crate::font::{DefaultFont, Font5x5};
+use crate::led_matrix_text::{LedMatrixText, Priority};
+use kernel::debug;
+use kernel::hil::led::Led;
+use kernel::hil::time::Alarm;
+use kernel::process::{Error, ProcessId};
+use kernel::syscall::{CommandReturn, SyscallDriver};
+use kernel::ErrorCode;
+
+/// The driver number
+///
+/// As this is not one of Tock's standard drivers,
+/// its number has to be higher or equal to 0xa0000.
+///
+/// Our previous driver was 0xa0005 so we use the
+/// number available.
+pub const DRIVER_NUM: usize = 0xa0006;
+
+/// The short code displayed for errors that are not in the table
+const UNKNOWN_CODE: &str = "E00";
+
+/// A reporter of the errors returned by the drivers
+///
+/// Kernel capsules use it to report their errors
+/// without depending on the display's types.
+pub trait ErrorReporter {
+    /// Reports an error returned by a driver
+    fn report(&self, driver: usize, error: ErrorCode);
+}
+
+/// The short code and the description of an error returned by a driver
+pub struct ErrorMessage {
+    /// The number of the driver that returned the error
+    pub driver: usize,
+    /// The error returned by the driver
+    pub error: ErrorCode,
+    /// The short code displayed on the LED matrix
+    pub code: &'static str,
+    /// The description printed on the console
+    pub description: &'static str,
+}
+
+/// The driver number of the entries that apply to all the drivers
+///
+/// These are the errors returned by the kernel itself, for instance
+/// for a driver that the supervisor has disabled. The entries of a
+/// driver take precedence over them.
+pub const ANY_DRIVER: usize = usize::MAX;
+
+/// The errors returned by the drivers of this book
+pub static ERROR_MESSAGES: [ErrorMessage; 14] = [
+    ErrorMessage {
+        driver: ANY_DRIVER,
+        error: ErrorCode::NOSUPPORT,
+        code: "E01",
+        description: "unknown command",
+    },
+    ErrorMessage {
+        driver: ANY_DRIVER,
+        error: ErrorCode::NODEVICE,
+        code: "E02",
+        description: "the driver is disabled by the supervisor or not present",
+    },
+    ErrorMessage {
+        driver: crate::led_matrix_text::DRIVER_NUM,
+        error: ErrorCode::INVAL,
+        code: "E10",
+        description: "invalid text attribute, display mode, statistic or setting",
+    },
+    ErrorMessage {
+        driver: crate::led_matrix_text::DRIVER_NUM,
+        error: ErrorCode::BUSY,
+        code: "E11",
+        description: "the display is busy with another request or animation",
+    },
+    ErrorMessage {
+        driver: crate::led_matrix_text::DRIVER_NUM,
+        error: ErrorCode::SIZE,
+        code: "E12",
+        description: "the text does not fit in the display's buffer",
+    },
+    ErrorMessage {
+        driver: crate::driver_toggle::DRIVER_NUM,
+        error: ErrorCode::NODEVICE,
+        code: "E20",
+        description: "only the supervisor process can toggle drivers",
+    },
+    ErrorMessage {
+        driver: crate::driver_toggle::DRIVER_NUM,
+        error: ErrorCode::INVAL,
+        code: "E21",
+        description: "the driver cannot be toggled",
+    },
+    ErrorMessage {
+        driver: crate::heartbeat::DRIVER_NUM,
+        error: ErrorCode::OFF,
+        code: "E30",
+        description: "a process missed too many heartbeats and was stopped",
+    },
+    ErrorMessage {
+        driver: crate::heartbeat::DRIVER_NUM,
+        error: ErrorCode::INVAL,
+        code: "E31",
+        description: "heartbeat sent by a process that the board does not supervise",
+    },
+    ErrorMessage {
+        driver: crate::heartbeat::DRIVER_NUM,
+        error: ErrorCode::FAIL,
+        code: "E32",
+        description: "a process missed its heartbeat and was restarted",
+    },
+    ErrorMessage {
+        driver: crate::board_info::DRIVER_NUM,
+        error: ErrorCode::INVAL,
+        code: "E40",
+        description: "there is no board list element at this index",
+    },
+    ErrorMessage {
+        driver: crate::board_info::DRIVER_NUM,
+        error: ErrorCode::RESERVE,
+        code: "E41",
+        description: "no buffer allowed for the board description",
+    },
+    ErrorMessage {
+        driver: crate::gpio_pattern::DRIVER_NUM,
+        error: ErrorCode::INVAL,
+        code: "E50",
+        description: "invalid pin, pattern length or period",
+    },
+    ErrorMessage {
+        driver: crate::character_tones::DRIVER_NUM,
+        error: ErrorCode::INVAL,
+        code: "E60",
+        description: "the tones can only be enabled (1) or disabled (0)",
+    },
+];
+
+/// Structure representing the driver
+pub struct ErrorMessages<'a, L: Led, A: Alarm<'a>, F: Font5x5 = DefaultFont> {
+    /// The driver used to display the short codes
+    display: &'a LedMatrixText<'a, L, A, F>,
+
+    /// The table of known errors
+    messages: &'a [ErrorMessage],
+}
+
+impl<'a, L: Led, A: Alarm<'a>, F: Font5x5> ErrorMessages<'a, L, A, F> {
+    /// Initializes a new driver structure
+    pub fn new(display: &'a LedMatrixText<'a, L, A, F>, messages: &'a [ErrorMessage]) -> Self {
+        ErrorMessages { display, messages }
+    }
+
+    /// Reports an error identified by its number, as received from a process
+    fn report_error_number(&self, driver: usize, error: usize) {
+        let find = |driver: usize| {
+            self.messages
+                .iter()
+                .find(|message| message.driver == driver && message.error as usize == error)
+        };
+        match find(driver).or_else(|| find(ANY_DRIVER)) {
+            Some(message) => {
+                debug!(
+                    "{}: {} (driver {:#x}, {:?})",
+                    message.code, message.description, driver, message.error
+                );
+                let _ = self.display.print_str(message.code, Priority::Warning);
+            }
+            None => {
+                debug!(
+                    "{}: unknown error {} (driver {:#x})",
+                    UNKNOWN_CODE, error, driver
+                );
+                let _ = self.display.print_str(UNKNOWN_CODE, Priority::Warning);
+            }
+        }
+    }
+}
+
+/// This implementation allows the kernel capsules to report
+/// their errors through `ErrorMessages`.
+impl<'a, L: Led, A: Alarm<'a>, F: Font5x5> ErrorReporter for ErrorMessages<'a, L, A, F> {
+    /// The short code is displayed on the LED matrix and
+    /// expanded with its description on the console.
+    fn report(&self, driver: usize, error: ErrorCode) {
+        self.report_error_number(driver, error as usize);
+    }
+}
+
+/// The implementation of `SyscallDriver` makes `ErrorMessages` a syscall driver
+impl<'a, L: Led, A: Alarm<'a>, F: Font5x5> SyscallDriver for ErrorMessages<'a, L, A, F> {
+    fn allocate_grant(&self, _: ProcessId) -> Result<(), Error> {
+        // there is no grant used by this driver, we just ignore
+        // the function call and return success
+        Ok(())
+    }
+
+    fn command(
+        &self,
+        command_number: usize,
+        r2: usize,
+        r3: usize,
+        _process_id: ProcessId,
+    ) -> CommandReturn {
+        match command_number {
+            // Tock's convention states that all syscall drivers must return *success* or *success_...* for
+            // command number 0. This allows processes to verify if a driver is present.
+            0 => CommandReturn::success(),
+            // Report an error received by the process
+            //  r2 - the number of the driver that returned the error
+            //  r3 - the error code
+            1 => {
+                self.report_error_number(r2, r3);
+                CommandReturn::success()
+            }
+            // Inform the process that we do not understand the command
+            _ => CommandReturn::failure(ErrorCode::NOSUPPORT),
+        }
+    }
+
+    /* the default implementation of the *allow_...* functions is used */
+}
@@ -0,0 +1,247 @@
+use crate::process_slot::{self, Processes};
+use crate::time64::Time64;
+use core::cell::Cell;
+use kernel::capabilities::ProcessManagementCapability;
+use kernel::debug;
+use kernel::hil::time::{Alarm, AlarmClient};
+use kernel::process::{FaultAction, Process, ProcessFaultPolicy, ProcessId};
+use kernel::utilities::cells::OptionalCell;
+use kernel::Kernel;
+
+/// The completion code that the kernel records for a process
+/// that is restarted after a fault
+const FAULT_COMPLETION_CODE: u32 = 1;
+
+/// The maximum number of processes that can wait for a
+/// delayed restart at the same time
+const MAX_PENDING_RESTARTS: usize = 4;
+
+/// The maximum number of slots in the kernel's processes array
+const MAX_PROCESSES: usize = 4;
+
+/// What the kernel does when a process faults
+#[derive(Copy, Clone)]
+pub enum RestartPolicy {
+    /// Stop the process at its first fault.
+    ///
+    /// This is the policy used for security critical processes,
+    /// as restarting them might give an attacker another try.
+    Stop,
+    /// Restart the process at most `max_restarts` times and
+    /// stop it afterwards.
+    ///
+    /// The process is restarted `backoff_ms` milliseconds after
+    /// its first fault, the delay doubles after each restart.
+    /// A `backoff_ms` of 0 restarts the process immediately.
+    Restart {
+        max_restarts: usize,
+        backoff_ms: u32,
+    },
+}
+
+/// Structure representing the fault policy manager
+pub struct FaultPolicyManager<'a, A: Alarm<'a>, C: ProcessManagementCapability> {
+    /// A reference to the kernel, used to find the processes
+    /// that have to be restarted
+    kernel: &'static Kernel,
+
+    /// The alarm used to implement the delayed restarts
+    alarm: &'a A,
+
+    /// The time that the restarts are scheduled in
+    time: &'a Time64<'a, A>,
+
+    /// The kernel's processes array
+    processes: &'a Processes,
+
+    /// The restart policies for each process, identified by its slot in `processes`
+    ///
+    /// The process name is chosen by the process itself, so any process
+    /// could claim the policy of another one, the slot is decided by the board.
+    policies: &'a [(usize, RestartPolicy)],
+
+    /// The restart policy for the processes that are not in `policies`
+    default_policy: RestartPolicy,
+
+    /// The processes waiting for a delayed restart, together with the
+    /// time (in ticks since the board started) of the restart
+    ///
+    /// The 64 bit time does not wrap around, so backoffs longer than
+    /// the wrap around of the alarm's counter are not cut short.
+    pending: [OptionalCell<(ProcessId, u64)>; MAX_PENDING_RESTARTS],
+
+    /// The number of times the process in each slot of `processes`
+    /// has been restarted after a fault
+    ///
+    /// The kernel's restart count also includes the restarts made by
+    /// other drivers (like the heartbeat), so it is not used to
+    /// decide when to stop a process.
+    restarts: [Cell<usize>; MAX_PROCESSES],
+
+    /// The capability that allows the manager to restart processes
+    capability: C,
+}
+
+impl<'a, A: Alarm<'a>, C: ProcessManagementCapability> FaultPolicyManager<'a, A, C> {
+    /// Initializes a new fault policy manager structure
+    pub fn new(
+        kernel: &'static Kernel,
+        alarm: &'a A,
+        time: &'a Time64<'a, A>,
+        processes: &'a Processes,
+        policies: &'a [(usize, RestartPolicy)],
+        default_policy: RestartPolicy,
+        capability: C,
+    ) -> Self {
+        if processes.len() > MAX_PROCESSES {
+            panic!(
+                "Expecting at most {} processes, {} supplied",
+                MAX_PROCESSES,
+                processes.len()
+            );
+        }
+        FaultPolicyManager {
+            kernel,
+            alarm,
+            time,
+            processes,
+            policies,
+            default_policy,
+            pending: [
+                OptionalCell::empty(),
+                OptionalCell::empty(),
+                OptionalCell::empty(),
+                OptionalCell::empty(),
+            ],
+            restarts: [Cell::new(0), Cell::new(0), Cell::new(0), Cell::new(0)],
+            capability,
+        }
+    }
+
+    /// Returns the restart policy for a process
+    fn policy_for(&self, slot: Option<usize>) -> RestartPolicy {
+        self.policies
+            .iter()
+            .find(|(policy_slot, _)| Some(*policy_slot) == slot)
+            .map_or(self.default_policy, |(_, policy)| *policy)
+    }
+
+    /// Counts a restart of the process in a slot
+    fn count_restart(&self, slot: Option<usize>) {
+        if let Some(slot) = slot {
+            self.restarts[slot].set(self.restarts[slot].get() + 1);
+        }
+    }
+
+    /// Schedules a delayed restart for a process
+    ///
+    /// Returns `false` if there is no free slot to store the restart.
+    fn schedule_restart(&self, process_id: ProcessId, delay_ms: u32) -> bool {
+        match self.pending.iter().find(|pending| pending.is_none()) {
+            Some(slot) => {
+                slot.set((
+                    process_id,
+                    self.time.now_ticks() + self.time.ticks_from_ms(delay_ms),
+                ));
+                self.set_next_alarm();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Sets the alarm for the closest pending restart
+    fn set_next_alarm(&self) {
+        let mut next: Option<u64> = None;
+        for pending in self.pending.iter() {
+            pending.map(|(_, restart)| {
+                next = match next {
+                    Some(closest) if closest <= *restart => Some(closest),
+                    _ => Some(*restart),
+                };
+            });
+        }
+        if let Some(restart) = next {
+            self.alarm
+                .set_alarm(self.alarm.now(), self.time.alarm_interval(restart));
+        }
+    }
+}
+
+/// This implementation allows the kernel to ask `FaultPolicyManager`
+/// what to do when a process faults.
+impl<'a, A: Alarm<'a>, C: ProcessManagementCapability> ProcessFaultPolicy
+    for FaultPolicyManager<'a, A, C>
+{
+    fn action(&self, process: &dyn Process) -> FaultAction {
+        let process_name = process.get_process_name();
+        let slot = process_slot::slot_of(self.processes, process);
+        match self.policy_for(slot) {
+            RestartPolicy::Stop => {
+                debug!("{}: fault, stopped (security critical)", process_name);
+                FaultAction::Stop
+            }
+            RestartPolicy::Restart {
+                max_restarts,
+                backoff_ms,
+            } => {
+                let restarts = slot.map_or(0, |slot| self.restarts[slot].get());
+                if restarts >= max_restarts {
+                    debug!(
+                        "{}: fault, stopped after {} restarts",
+                        process_name, restarts
+                    );
+                    FaultAction::Stop
+                } else if backoff_ms == 0 {
+                    self.count_restart(slot);
+                    debug!("{}: fault, restarted", process_name);
+                    FaultAction::Restart
+                } else {
+                    // Double the delay for every previous restart, making
+                    // sure that we do not overflow.
+                    let delay_ms = backoff_ms.saturating_mul(1 << restarts.min(16));
+                    if self.schedule_restart(process.processid(), delay_ms) {
+                        self.count_restart(slot);
+                        debug!("{}: fault, restart in {} ms", process_name, delay_ms);
+                    } else {
+                        debug!(
+                            "{}: fault, stopped (too many pending restarts)",
+                            process_name
+                        );
+                    }
+                    // The process stays stopped until its restart is due.
+                    FaultAction::Stop
+                }
+            }
+        }
+    }
+}
+
+/// This implementation allows `FaultPolicyManager` to use an alarm.
+impl<'a, A: Alarm<'a>, C: ProcessManagementCapability> AlarmClient
+    for FaultPolicyManager<'a, A, C>
+{
+    /// Called when the alarm expires
+    fn alarm(&self) {
+        let now = self.time.now_ticks();
+        for pending in self.pending.iter() {
+            // Verify if the restart is due
+            let due = pending.map_or(false, |(_, restart)| now >= *restart);
+            if due {
+                if let Some((process_id, _)) = pending.take() {
+                    // Search the process, it might have been
+                    // removed in the meantime.
+                    self.kernel
+                        .process_each_capability(&self.capability, |process| {
+                            if process.processid() == process_id {
+                                debug!("{}: restarted", process.get_process_name());
+                                process.try_restart(FAULT_COMPLETION_CODE);
+                            }
+                        });
+                }
+            }
+        }
+        // Set the alarm for the remaining restarts
+        self.set_next_alarm();
+    }
+}
@@ -0,0 +1,154 @@
+// The glyph tables (`DIGITS`, `LETTERS`, `LOWERCASE`, `PUNCTUATION` and `ICONS`) are generated
+// at compile time from the text art in `fonts/font5x5.txt` by the driver's `build.rs`.
+include!(concat!(env!("OUT_DIR"), "/font5x5.rs"));
+
+/// The characters displayed by the glyphs of the `PUNCTUATION` table,
+/// in the order of the glyphs
+const PUNCTUATION_CHARACTERS: [char; 8] = [' ', '.', ',', '!', '?', ':', '-', '+'];
+
+/// The control byte that displays a heart
+pub const ICON_HEART: u8 = 0x01;
+/// The control byte that displays a check mark
+pub const ICON_CHECK: u8 = 0x02;
+/// The control byte that displays a cross
+pub const ICON_CROSS: u8 = 0x03;
+/// The control byte that displays an arrow pointing up
+pub const ICON_ARROW_UP: u8 = 0x04;
+/// The control byte that displays an arrow pointing down
+pub const ICON_ARROW_DOWN: u8 = 0x05;
+/// The control byte that displays an arrow pointing left
+pub const ICON_ARROW_LEFT: u8 = 0x06;
+/// The control byte that displays an arrow pointing right
+pub const ICON_ARROW_RIGHT: u8 = 0x07;
+
+/// The number of bits of a glyph
+const GLYPH_BITS: usize = 25;
+
+/// Returns the number of bytes of a table of `glyphs` packed glyphs
+const fn packed_len(glyphs: usize) -> usize {
+    let bits = glyphs * GLYPH_BITS;
+    // The last byte is padded with zeros.
+    bits / 8 + if bits % 8 == 0 { 0 } else { 1 }
+}
+
+/// Verifies the generated tables at compile time, it is never called
+///
+/// Each table must have the packed size of the glyphs that the driver
+/// expects, so a glyph missing from or added to `fonts/font5x5.txt` stops
+/// the build with a type mismatch. Taking a shared reference outside of
+/// an `unsafe` block also compiles only if the tables are `static` and
+/// not `static mut`, so they stay in flash and are not copied to RAM.
+#[allow(dead_code)]
+fn check_tables() {
+    let _: &'static [u8; packed_len(10)] = &DIGITS;
+    let _: &'static [u8; packed_len(26)] = &LETTERS;
+    let _: &'static [u8; packed_len(26)] = &LOWERCASE;
+    let _: &'static [u8; packed_len(PUNCTUATION_CHARACTERS.len())] = &PUNCTUATION;
+    let _: &'static [u8; packed_len((ICON_ARROW_RIGHT - ICON_HEART + 1) as usize)] = &ICONS;
+}
+
+/// Returns the glyph found at `index` in a packed glyph table
+///
+/// The glyphs of the generated tables are packed, every glyph
+/// uses `GLYPH_BITS` bits starting with the most significant bit.
+fn unpack(table: &[u8], index: usize) -> u32 {
+    let start = index * GLYPH_BITS;
+    (start..start + GLYPH_BITS).fold(0, |glyph, bit| {
+        (glyph << 1) | ((table[bit / 8] >> (7 - bit % 8)) & 0x01) as u32
+    })
+}
+
+/// Converts five rows of text art into a glyph
+///
+/// Every row has 5 pixels, `#` is an LED that is on and `.` is an LED
+/// that is off. The first row is the top row of the LED matrix. The
+/// format is the same as the one of `fonts/font5x5.txt`, for glyphs
+/// defined in the code, such as the glyphs of a board's own font.
+///
+/// ```ignore
+/// const SMILE: u32 = drivers::font!(
+///     ".....",
+///     ".#.#.",
+///     ".....",
+///     "#...#",
+///     ".###.",
+/// );
+/// ```
+#[macro_export]
+macro_rules! font {
+    ($row0:expr, $row1:expr, $row2:expr, $row3:expr, $row4:expr $(,)?) => {
+        $crate::font::glyph_from_rows([$row0, $row1, $row2, $row3, $row4])
+    };
+}
+
+/// Converts five rows of text art into a glyph, used by the `font!` macro
+///
+/// In a constant, a row that does not have 5 pixels or a pixel that
+/// is neither `#` nor `.` stops the compilation with an error.
+pub const fn glyph_from_rows(rows: [&str; 5]) -> u32 {
+    let mut glyph = 0;
+    let mut row = 0;
+    while row < 5 {
+        let pixels = rows[row].as_bytes();
+        if pixels.len() != 5 {
+            panic!("glyph rows must be 5 pixels of '#' or '.'");
+        }
+        let mut column = 0;
+        while column < 5 {
+            let pixel = pixels[column];
+            if pixel != b'#' && pixel != b'.' {
+                panic!("glyph rows must be 5 pixels of '#' or '.'");
+            }
+            glyph = (glyph << 1) | (pixel == b'#') as u32;
+            column += 1;
+        }
+        row += 1;
+    }
+    glyph
+}
+
+/// A transliteration table for the Latin-1 characters 0xC0 to 0xFF
+///
+/// Every element is the ASCII character displayed instead of the
+/// Latin-1 character, or 0 if the character has no replacement.
+pub type Transliteration = [u8; 64];
+
+/// The default transliteration, it replaces the accented
+/// letters with their base letters (É is displayed as E)
+pub const LATIN1_TRANSLITERATION: Transliteration = *b"AAAAAAACEEEEIIII\
+    DNOOOOOxOUUUUYPs\
+    aaaaaaaceeeeiiii\
+    dnooooo\0ouuuuypy";
+
+/// A font for the 5x5 LED matrix
+///
+/// A font glyph is a set of bits that represents the state of
+/// the LEDs, bit 24 is the upper left LED and bit 0 is the lower
+/// right LED. Boards can supply their own fonts (bold, narrow,
+/// non-Latin) to the `LedMatrixText` driver by implementing
+/// this trait.
+pub trait Font5x5 {
+    /// Returns the glyph of a character, `None` if the
+    /// font has no glyph for it
+    fn glyph(character: char) -> Option<u32>;
+}
+
+/// The default font, generated from `fonts/font5x5.txt`
+pub struct DefaultFont;
+
+impl Font5x5 for DefaultFont {
+    fn glyph(character: char) -> Option<u32> {
+        match character {
+            '0'..='9' => Some(unpack(&DIGITS, character as usize - '0' as usize)),
+            'A'..='Z' => Some(unpack(&LETTERS, character as usize - 'A' as usize)),
+            'a'..='z' => Some(unpack(&LOWERCASE, character as usize - 'a' as usize)),
+            _ if (ICON_HEART as u32..=ICON_ARROW_RIGHT as u32).contains(&(character as u32)) => {
+                Some(unpack(&ICONS, character as usize - ICON_HEART as usize))
+            }
+            _ => PUNCTUATION_CHARACTERS
+                .iter()
+                .position(|punctuation| *punctuation == character)
+                .map(|index| unpack(&PUNCTUATION, index)),
+        }
+    }
+}
@@ -0,0 +1,214 @@
+use crate::time64::Time64;
+use core::cell::Cell;
+use kernel::hil::gpio::Pin;
+use kernel::hil::time::{Alarm, AlarmClient};
+use kernel::process::{Error, ProcessId};
+use kernel::syscall::{CommandReturn, SyscallDriver};
+use kernel::ErrorCode;
+
+/// The driver number
+///
+/// As this is not one of Tock's standard drivers,
+/// its number has to be higher or equal to 0xa0000.
+///
+/// Our previous driver was 0xa0007 so we use the
+/// number available.
+pub const DRIVER_NUM: usize = 0xa0008;
+
+/// The maximum number of pins that can play patterns
+pub const MAX_PINS: usize = 4;
+
+/// A pattern played on a pin
+#[derive(Copy, Clone)]
+struct Pattern {
+    /// The on (1) and off (0) states, played starting with
+    /// the most significant of the `length` bits
+    bits: u32,
+    /// The number of states of the pattern
+    length: usize,
+    /// The position of the state that is played
+    position: usize,
+    /// The time (in ticks) each state is played
+    period: u64,
+    /// The time (in ticks since the board started) when the current
+    /// state started, the 64 bit time does not wrap around
+    reference: u64,
+}
+
+/// Structure representing the driver
+pub struct GpioPattern<'a, P: Pin, A: Alarm<'a>> {
+    /// The pins that can play patterns
+    pins: &'a [&'a P],
+
+    /// The alarm used to change the states of the pins
+    alarm: &'a A,
+
+    /// The time that the states are played in
+    time: &'a Time64<'a, A>,
+
+    /// The pattern played by each pin, `None` if the pin is idle
+    patterns: [Cell<Option<Pattern>>; MAX_PINS],
+}
+
+impl<'a, P: Pin, A: Alarm<'a>> GpioPattern<'a, P, A> {
+    /// Initializes a new driver structure
+    pub fn new(pins: &'a [&'a P], alarm: &'a A, time: &'a Time64<'a, A>) -> Self {
+        if pins.len() > MAX_PINS {
+            panic!(
+                "Expecting at most {} pins, {} supplied",
+                MAX_PINS,
+                pins.len()
+            );
+        }
+        GpioPattern {
+            pins,
+            alarm,
+            time,
+            patterns: [
+                Cell::new(None),
+                Cell::new(None),
+                Cell::new(None),
+                Cell::new(None),
+            ],
+        }
+    }
+
+    /// Sets the pin to the current state of its pattern
+    fn play(&self, index: usize, pattern: &Pattern) {
+        if (pattern.bits >> (pattern.length - 1 - pattern.position)) & 0x01 == 1 {
+            self.pins[index].set();
+        } else {
+            self.pins[index].clear();
+        }
+    }
+
+    /// Returns `true` if the current state of the pattern has been played long enough
+    fn is_expired(&self, now: u64, pattern: &Pattern) -> bool {
+        now >= pattern.reference + pattern.period
+    }
+
+    /// Sets the alarm for the closest change of state
+    fn set_next_alarm(&self) {
+        let mut next: Option<u64> = None;
+        for pattern in self.patterns.iter().filter_map(|pattern| pattern.get()) {
+            let change = pattern.reference + pattern.period;
+            next = match next {
+                Some(closest) if closest <= change => Some(closest),
+                _ => Some(change),
+            };
+        }
+        match next {
+            Some(change) => self
+                .alarm
+                .set_alarm(self.alarm.now(), self.time.alarm_interval(change)),
+            // No pin plays a pattern, there is no need for the alarm.
+            None => {
+                let _ = self.alarm.disarm();
+            }
+        }
+    }
+
+    /// Starts playing a pattern on a pin
+    fn start(
+        &self,
+        index: usize,
+        bits: u32,
+        length: usize,
+        period_ms: u32,
+    ) -> Result<(), ErrorCode> {
+        if index >= self.pins.len() || length == 0 || length > 32 || period_ms == 0 {
+            return Err(ErrorCode::INVAL);
+        }
+        let pattern = Pattern {
+            bits,
+            length,
+            position: 0,
+            period: self.time.ticks_from_ms(period_ms),
+            reference: self.time.now_ticks(),
+        };
+        self.pins[index].make_output();
+        self.play(index, &pattern);
+        self.patterns[index].set(Some(pattern));
+        self.set_next_alarm();
+        Ok(())
+    }
+
+    /// Stops the pattern played on a pin and turns the pin off
+    fn stop(&self, index: usize) -> Result<(), ErrorCode> {
+        if index >= self.pins.len() {
+            return Err(ErrorCode::INVAL);
+        }
+        self.patterns[index].set(None);
+        self.pins[index].clear();
+        self.set_next_alarm();
+        Ok(())
+    }
+}
+
+/// This implementation allows `GpioPattern` to use an alarm.
+impl<'a, P: Pin, A: Alarm<'a>> AlarmClient for GpioPattern<'a, P, A> {
+    /// Called when the alarm expires
+    fn alarm(&self) {
+        let now = self.time.now_ticks();
+        for (index, cell) in self.patterns.iter().enumerate() {
+            if let Some(mut pattern) = cell.get() {
+                if self.is_expired(now, &pattern) {
+                    // Move to the next state of the pattern
+                    pattern.position = (pattern.position + 1) % pattern.length;
+                    // Keep the states aligned to the period, unless we
+                    // are late with more than one period.
+                    let next = pattern.reference + pattern.period;
+                    pattern.reference = if now < next + pattern.period {
+                        next
+                    } else {
+                        now
+                    };
+                    self.play(index, &pattern);
+                    cell.set(Some(pattern));
+                }
+            }
+        }
+        // Set the alarm for the next change of state
+        self.set_next_alarm();
+    }
+}
+
+/// The implementation of `SyscallDriver` makes `GpioPattern` a syscall driver
+impl<'a, P: Pin, A: Alarm<'a>> SyscallDriver for GpioPattern<'a, P, A> {
+    fn allocate_grant(&self, _: ProcessId) -> Result<(), Error> {
+        // there is no grant used by this driver, we just ignore
+        // the function call and return success
+        Ok(())
+    }
+
+    fn command(
+        &self,
+        command_number: usize,
+        r2: usize,
+        r3: usize,
+        _process_id: ProcessId,
+    ) -> CommandReturn {
+        match command_number {
+            // Tock's convention states that all syscall drivers must return *success* or *success_...* for
+            // command number 0. This allows processes to verify if a driver is present.
+            // We also return the number of pins.
+            0 => CommandReturn::success_u32(self.pins.len() as u32),
+            // Start playing a pattern
+            //  r2 - bits 0 to 7: the pin, bits 8 to 15: the number of states (1 to 32),
+            //       bits 16 to 31: the time in milliseconds each state is played
+            //  r3 - the states, 1 is on and 0 is off, starting with the most significant
+            1 => {
+                let index = r2 & 0xff;
+                let length = (r2 >> 8) & 0xff;
+                let period_ms = ((r2 >> 16) & 0xffff) as u32;
+                self.start(index, r3 as u32, length, period_ms).into()
+            }
+            // Stop the pattern played on the pin r2
+            2 => self.stop(r2).into(),
+            // Inform the process that we do not understand the command
+            _ => CommandReturn::failure(ErrorCode::NOSUPPORT),
+        }
+    }
+
+    /* the default implementation of the *allow_...* functions is used */
+}
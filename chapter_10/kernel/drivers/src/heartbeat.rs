@@ -0,0 +1,259 @@
+use crate::error_messages::ErrorReporter;
+use crate::process_slot::Processes;
+use crate::time64::Time64;
+use core::cell::Cell;
+use kernel::debug;
+use kernel::hil::time::{Alarm, AlarmClient};
+use kernel::process::{Error, ProcessId};
+use kernel::syscall::{CommandReturn, SyscallDriver};
+use kernel::utilities::cells::OptionalCell;
+use kernel::ErrorCode;
+
+/// The driver number
+///
+/// As this is not one of Tock's standard drivers,
+/// its number has to be higher or equal to 0xa0000.
+///
+/// Our previous driver was 0xa0004 so we use the
+/// number available.
+pub const DRIVER_NUM: usize = 0xa0005;
+
+/// The completion code that the kernel records for a process
+/// that is restarted or stopped as it has missed its heartbeat
+const MISSED_HEARTBEAT_COMPLETION_CODE: u32 = 2;
+
+/// The shortest time in milliseconds that the board may ask for between
+/// two heartbeats, so that a process is not restarted over and over
+const MIN_TIMEOUT_MS: u32 = 100;
+
+/// The maximum number of processes that the driver supervises
+const MAX_SUPERVISED: usize = 4;
+
+/// A process that has to send heartbeats, decided by the board
+#[derive(Copy, Clone)]
+pub struct Supervision {
+    /// The slot of the process in the kernel's processes array
+    pub slot: usize,
+    /// The maximum time in milliseconds between two heartbeats,
+    /// at least `MIN_TIMEOUT_MS`
+    pub timeout_ms: u32,
+    /// The number of times the process is restarted for missing its
+    /// heartbeat, it is stopped when it misses it once more
+    ///
+    /// These restarts are counted by the driver, they do not use up
+    /// the restarts that the fault policy allows after a fault.
+    pub max_restarts: usize,
+}
+
+/// Structure representing the driver
+pub struct Heartbeat<'a, A: Alarm<'a>> {
+    /// The alarm used to verify the deadlines
+    alarm: &'a A,
+
+    /// The time that the deadlines are measured in
+    time: &'a Time64<'a, A>,
+
+    /// The kernel's processes array
+    processes: &'a Processes,
+
+    /// The processes that have to send heartbeats
+    supervised: &'a [Supervision],
+
+    /// The time (in ticks since the board started) until which each of the
+    /// `supervised` processes has to send its next heartbeat, `None` if the
+    /// process has been stopped
+    ///
+    /// The 64 bit time does not wrap around, so timeouts longer than
+    /// the wrap around of the alarm's counter are not cut short.
+    deadlines: [Cell<Option<u64>>; MAX_SUPERVISED],
+
+    /// The number of times each of the `supervised` processes has been
+    /// restarted for missing its heartbeat
+    restarts: [Cell<usize>; MAX_SUPERVISED],
+
+    /// An optional reporter that displays the missed heartbeats
+    errors: OptionalCell<&'a dyn ErrorReporter>,
+}
+
+impl<'a, A: Alarm<'a>> Heartbeat<'a, A> {
+    /// Initializes a new driver structure
+    pub fn new(
+        alarm: &'a A,
+        time: &'a Time64<'a, A>,
+        processes: &'a Processes,
+        supervised: &'a [Supervision],
+    ) -> Self {
+        if supervised.len() > MAX_SUPERVISED {
+            panic!(
+                "Expecting at most {} supervised processes, {} supplied",
+                MAX_SUPERVISED,
+                supervised.len()
+            );
+        }
+        if let Some(supervision) = supervised
+            .iter()
+            .find(|supervision| supervision.timeout_ms < MIN_TIMEOUT_MS)
+        {
+            panic!(
+                "The heartbeat timeout of slot {} is shorter than {} ms",
+                supervision.slot, MIN_TIMEOUT_MS
+            );
+        }
+        Heartbeat {
+            alarm,
+            time,
+            processes,
+            supervised,
+            deadlines: [
+                Cell::new(None),
+                Cell::new(None),
+                Cell::new(None),
+                Cell::new(None),
+            ],
+            restarts: [Cell::new(0), Cell::new(0), Cell::new(0), Cell::new(0)],
+            errors: OptionalCell::empty(),
+        }
+    }
+
+    /// Starts supervising the processes
+    ///
+    /// The board has to call this after setting `Heartbeat` as the
+    /// alarm's client. Each process has to send its first heartbeat
+    /// within its timeout, so a process that hangs before sending
+    /// any heartbeat is restarted too.
+    pub fn start(&self) {
+        let now = self.time.now_ticks();
+        for (index, supervision) in self.supervised.iter().enumerate() {
+            self.deadlines[index].set(Some(now + self.time.ticks_from_ms(supervision.timeout_ms)));
+        }
+        self.set_next_alarm();
+    }
+
+    /// Sets the reporter that displays the missed heartbeats
+    pub fn set_error_reporter(&self, errors: &'a dyn ErrorReporter) {
+        self.errors.set(errors);
+    }
+
+    /// Returns the position in `supervised` of a process
+    fn supervision_of(&self, process_id: ProcessId) -> Option<usize> {
+        self.supervised.iter().position(|supervision| {
+            self.processes
+                .get(supervision.slot)
+                .and_then(|process| *process)
+                .map_or(false, |process| process.processid() == process_id)
+        })
+    }
+
+    /// Sets the alarm for the closest deadline
+    fn set_next_alarm(&self) {
+        let next = self
+            .deadlines
+            .iter()
+            .filter_map(|deadline| deadline.get())
+            .min();
+        match next {
+            Some(deadline) => self
+                .alarm
+                .set_alarm(self.alarm.now(), self.time.alarm_interval(deadline)),
+            // No process is supervised, there is no need for the alarm.
+            None => {
+                let _ = self.alarm.disarm();
+            }
+        }
+    }
+}
+
+/// This implementation allows `Heartbeat` to use an alarm.
+impl<'a, A: Alarm<'a>> AlarmClient for Heartbeat<'a, A> {
+    /// Called when the alarm expires
+    fn alarm(&self) {
+        let now = self.time.now_ticks();
+        for (index, supervision) in self.supervised.iter().enumerate() {
+            if !self.deadlines[index]
+                .get()
+                .map_or(false, |deadline| now >= deadline)
+            {
+                continue;
+            }
+            let process = self
+                .processes
+                .get(supervision.slot)
+                .and_then(|process| *process);
+            let restarts = self.restarts[index].get();
+            if restarts < supervision.max_restarts {
+                // The restarted process has a whole timeout for its first heartbeat.
+                self.restarts[index].set(restarts + 1);
+                self.deadlines[index]
+                    .set(Some(now + self.time.ticks_from_ms(supervision.timeout_ms)));
+                if let Some(process) = process {
+                    debug!(
+                        "{}: missed heartbeat, restarted",
+                        process.get_process_name()
+                    );
+                    process.try_restart(MISSED_HEARTBEAT_COMPLETION_CODE);
+                }
+                self.errors
+                    .map(|errors| errors.report(DRIVER_NUM, ErrorCode::FAIL));
+            } else {
+                self.deadlines[index].set(None);
+                if let Some(process) = process {
+                    debug!(
+                        "{}: missed heartbeat, stopped after {} restarts",
+                        process.get_process_name(),
+                        restarts
+                    );
+                    process.terminate(MISSED_HEARTBEAT_COMPLETION_CODE);
+                }
+                self.errors
+                    .map(|errors| errors.report(DRIVER_NUM, ErrorCode::OFF));
+            }
+        }
+        // Set the alarm for the remaining deadlines
+        self.set_next_alarm();
+    }
+}
+
+/// The implementation of `SyscallDriver` makes `Heartbeat` a syscall driver
+impl<'a, A: Alarm<'a>> SyscallDriver for Heartbeat<'a, A> {
+    fn allocate_grant(&self, _: ProcessId) -> Result<(), Error> {
+        // there is no grant used by this driver, we just ignore
+        // the function call and return success
+        Ok(())
+    }
+
+    fn command(
+        &self,
+        command_number: usize,
+        _r2: usize,
+        _r3: usize,
+        process_id: ProcessId,
+    ) -> CommandReturn {
+        match command_number {
+            // Tock's convention states that all syscall drivers must return *success* or *success_...* for
+            // command number 0. This allows processes to verify if a driver is present.
+            0 => CommandReturn::success(),
+            // Heartbeat, the process is still alive
+            1 => match self.supervision_of(process_id) {
+                Some(index) => match self.deadlines[index].get() {
+                    Some(_) => {
+                        // Move the deadline
+                        let timeout = self.supervised[index].timeout_ms;
+                        self.deadlines[index].set(Some(
+                            self.time.now_ticks() + self.time.ticks_from_ms(timeout),
+                        ));
+                        self.set_next_alarm();
+                        CommandReturn::success()
+                    }
+                    // The board has not started the supervision
+                    None => CommandReturn::failure(ErrorCode::OFF),
+                },
+                // The board does not supervise this process
+                None => CommandReturn::failure(ErrorCode::INVAL),
+            },
+            // Inform the process that we do not understand the command
+            _ => CommandReturn::failure(ErrorCode::NOSUPPORT),
+        }
+    }
+
+    /* the default implementation of the *allow_...* functions is used */
+}
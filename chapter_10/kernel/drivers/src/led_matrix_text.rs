@@ -1,12 +1,18 @@
+use crate::buffer_pool::BufferPool;
+use crate::font::{DefaultFont, Font5x5, Transliteration};
 use core::cell::Cell;
 use core::cmp;
+use core::marker::PhantomData;
+use core::mem;
 use kernel::dynamic_deferred_call::{
     DeferredCallHandle, DynamicDeferredCall, DynamicDeferredCallClient,
 };
+use kernel::grant::Grant;
 use kernel::hil::led::Led;
 use kernel::hil::text_screen::{TextScreen, TextScreenClient};
-use kernel::hil::time::{Alarm, AlarmClient, ConvertTicks};
+use kernel::hil::time::{Alarm, AlarmClient, ConvertTicks, Ticks};
 use kernel::process::{Error, ProcessId};
+use kernel::processbuffer::{ReadOnlyProcessBuffer, ReadableProcessBuffer};
 use kernel::syscall::{CommandReturn, SyscallDriver};
 use kernel::utilities::cells::{OptionalCell, TakeCell};
 use kernel::ErrorCode;
@@ -20,92 +26,205 @@ use kernel::ErrorCode;
 /// number available.
 pub const DRIVER_NUM: usize = 0xa0003;
 
-/// Font glyph definition for digits
-///
-/// A font glyph is a set of bits that represents that
-/// state of the LEDs
-const DIGITS: [u32; 10] = [
-    // 0
-    0b11111_10011_10101_11001_11111,
-    // 1
-    0b00100_01100_00100_00100_01110,
-    // 2
-    0b11110_00001_01110_10000_11111,
-    // 3
-    0b11110_00001_11110_00001_11110,
-    // 4
-    0b10000_10000_10100_11111_00100,
-    // 5
-    0b11111_10000_11110_00001_11110,
-    // 6
-    0b11111_10000_11111_10001_11111,
-    // 7
-    0b11111_00001_00010_00100_00100,
-    // 8
-    0b11111_10001_11111_10001_11111,
-    // 9
-    0b11111_10001_11111_00001_11111,
+/// A frame with all the LEDs on
+const FULL_FRAME: u32 = 0b11111_11111_11111_11111_11111;
+
+/// Text attribute: blink the whole message, every character
+/// is displayed for half of the time and hidden for the other half
+pub const ATTRIBUTE_BLINK: usize = 1 << 0;
+
+/// Text attribute: inverse video, the lit and unlit
+/// LEDs of the characters are swapped
+pub const ATTRIBUTE_INVERSE: usize = 1 << 1;
+
+/// Text attribute: flash the whole matrix once when
+/// the message is printed
+pub const ATTRIBUTE_FLASH: usize = 1 << 2;
+
+/// All the known text attributes
+const ATTRIBUTES: usize = ATTRIBUTE_BLINK | ATTRIBUTE_INVERSE | ATTRIBUTE_FLASH;
+
+/// In word mode, the letters and digits are displayed
+/// this many times faster than the display speed
+const WORD_MODE_SPEEDUP: u32 = 2;
+
+/// In word mode, the pause between two words is this
+/// many times longer than the display speed
+const WORD_PAUSE_FACTOR: u32 = 3;
+
+/// In Morse mode, the duration of a dot is the
+/// display speed divided by this value
+const MORSE_UNIT_DIVIDER: u32 = 4;
+
+/// In Morse mode, the duration (in dots) of a dash
+/// and of the pause between two characters
+const MORSE_DASH_UNITS: u32 = 3;
+
+/// In Morse mode, the duration (in dots) of the pause between two words
+const MORSE_WORD_UNITS: u32 = 7;
+
+/// The Morse codes of the letters
+const MORSE_LETTERS: [&str; 26] = [
+    ".-", "-...", "-.-.", "-..", ".", "..-.", "--.", "....", "..", ".---", "-.-", ".-..", "--",
+    "-.", "---", ".--.", "--.-", ".-.", "...", "-", "..-", "...-", ".--", "-..-", "-.--", "--..",
 ];
 
-/// Font glyph definition for capital letters
-///
-/// A font glyph is a set of bits that represents that
-/// state of the LEDs
-const LETTERS: [u32; 26] = [
-    // A
-    0b01110_10001_11111_10001_10001,
-    // B
-    0b11111_10001_11110_10001_11111,
-    // C
-    0b11111_10000_10000_10000_11111,
-    // D
-    0b11110_10001_10001_10001_11110,
-    // E
-    0b11111_10000_11110_10000_11111,
-    // F
-    0b11111_10000_11110_10000_10000,
-    // G
-    0b11111_10000_10111_10001_11111,
-    // H
-    0b10001_10001_11111_10001_10001,
-    // I
-    0b11111_00100_00100_00100_11111,
-    // J
-    0b00011_00001_00001_10001_11111,
-    // K
-    0b10001_10010_11100_10010_10001,
-    // L
-    0b10000_10000_10000_10000_11111,
-    // M
-    0b10001_11011_10101_10001_10001,
-    // N
-    0b10001_11001_10101_10011_10001,
-    // O
-    0b01110_10001_10001_10001_01110,
-    // P
-    0b11110_10001_11110_10000_10000,
-    // Q
-    0b01110_10001_10001_01110_00011,
-    // R
-    0b11110_10001_11110_10001_10001,
-    // S
-    0b11111_10000_11111_00001_11111,
-    // T
-    0b11111_00100_00100_00100_00100,
-    // U
-    0b10001_10001_10001_10001_11111,
-    // V
-    0b10001_10001_01010_01010_00100,
-    // W
-    0b10001_10001_10101_10101_01010,
-    // X
-    0b10001_01010_00100_01010_10001,
-    // Y
-    0b10001_10001_01010_00100_00100,
-    // Z
-    0b11111_00010_00100_01000_11111,
+/// The Morse codes of the digits
+const MORSE_DIGITS: [&str; 10] = [
+    "-----", ".----", "..---", "...--", "....-", ".....", "-....", "--...", "---..", "----.",
 ];
 
+/// In the scroll modes, the number of steps in which a character slides in,
+/// one for each column (or row) and one for the space between characters
+///
+/// The transition modes use the same number of steps.
+const SCROLL_STEPS: usize = 6;
+
+/// The maximum number of times a character blinks while it is displayed
+///
+/// In the scroll modes, a character is visible for one step of each blink,
+/// so a blink cannot be shorter than a step.
+const MAX_BLINK_RATE: usize = SCROLL_STEPS;
+
+/// The bits of the first LED of each row, used to
+/// repeat a row mask for the whole matrix
+const FIRST_COLUMN: u32 = 0x108421;
+
+/// In the dissolve mode, the LED at `index` in the order in which the LEDs
+/// switch to the next character is `(index * DISSOLVE_STRIDE) % 25`
+///
+/// As the stride and 25 have no common divisor, every LED is selected once.
+const DISSOLVE_STRIDE: usize = 7;
+
+/// The number of characters during which the speed ramp
+/// moves from the start speed to the target speed
+const RAMP_CHARACTERS: usize = 8;
+
+/// The maximum number of decimals of a fixed point value
+const MAX_DECIMALS: usize = 9;
+
+/// The glyph displayed for the characters that the font does not
+/// have, until the board or a process sets another one
+const DEFAULT_PLACEHOLDER: u32 = crate::font!("#####", "#...#", "#...#", "#...#", "#####");
+
+/// The possible display modes
+#[derive(Copy, Clone, PartialEq)]
+enum Mode {
+    /// The text is displayed one letter or digit at a time
+    Continuous,
+    /// The text is displayed one word at a time, the letters and digits
+    /// of a word are displayed rapidly with a longer pause between words
+    Words,
+    /// The text is displayed in Morse code, the whole
+    /// matrix is lit for the dots and dashes
+    Morse,
+    /// The text scrolls smoothly from right to left,
+    /// one column at a time
+    Scroll,
+    /// The text scrolls smoothly from bottom to top,
+    /// one row at a time
+    ScrollUp,
+    /// The text scrolls smoothly from top to bottom,
+    /// one row at a time
+    ScrollDown,
+    /// The next character wipes the previous one,
+    /// one column at a time from left to right
+    Wipe,
+    /// The next character dissolves into the previous one,
+    /// a few LEDs at a time
+    Dissolve,
+}
+
+/// The displayed frame of the text, saved when the display is paused
+///
+/// The kernel messages displayed during the pause replace the frame,
+/// which is displayed again at the end of the message.
+#[derive(Copy, Clone)]
+struct HeldFrame {
+    /// The font glyph of the displayed character
+    glyph: u32,
+    /// In the scroll modes, the glyph of the previous character
+    previous_glyph: u32,
+    /// In the scroll modes, the step of the current character
+    scroll_step: usize,
+    /// The phase of the frame
+    phase: Phase,
+    /// In Morse mode, the character that is played
+    morse: Option<MorseCharacter>,
+}
+
+/// A character played in Morse code
+#[derive(Copy, Clone)]
+struct MorseCharacter {
+    /// The dots and dashes of the character
+    code: &'static str,
+    /// The position of the next dot or dash that is played
+    position: usize,
+}
+
+/// The possible phases of the displayed frame
+#[derive(Copy, Clone, PartialEq)]
+enum Phase {
+    /// The character is displayed
+    Visible,
+    /// The character is hidden (the message blinks)
+    Hidden,
+    /// All the LEDs are on (the message has just been printed)
+    Flash,
+    /// All the LEDs are on (a Morse dot or dash is played)
+    ///
+    /// This is not `Flash`, so that the flash of a new message
+    /// is not taken for a dot or dash of the Morse character.
+    Morse,
+    /// The matrix is blank between two characters
+    Gap,
+}
+
+/// The maximum length of the text printed by the processes
+pub const TEXT_LEN: usize = 50;
+
+/// The pool that provides the buffer for the text printed by the processes
+pub type TextBufferPool = BufferPool<1, TEXT_LEN>;
+
+/// The maximum length of a message printed by a kernel capsule
+pub const KERNEL_MESSAGE_LEN: usize = 16;
+
+/// The number of buffers used for kernel messages, one for the
+/// displayed message and one for a message that replaces it
+pub const KERNEL_MESSAGES: usize = 2;
+
+/// The pool of buffers used for kernel messages
+pub type KernelBufferPool = BufferPool<KERNEL_MESSAGES, KERNEL_MESSAGE_LEN>;
+
+/// The priority of a message printed by a kernel capsule
+#[derive(Copy, Clone, PartialEq, PartialOrd)]
+pub enum Priority {
+    /// An informative message
+    Info,
+    /// A warning, it replaces informative messages
+    Warning,
+    /// An alert, it replaces all the other messages
+    Alert,
+}
+
+/// The display statistics, they are reset on demand
+#[derive(Default)]
+struct Statistics {
+    /// The number of rendered frames
+    frames: Cell<u32>,
+    /// The number of messages that were displayed completely
+    completed: Cell<u32>,
+    /// The number of messages overwritten by a new message
+    overwritten: Cell<u32>,
+    /// The number of messages refused as the driver was busy
+    /// or the message was too long
+    dropped: Cell<u32>,
+    /// The total latency (in ticks) of the deferred callbacks
+    latency: Cell<u32>,
+    /// The number of deferred callbacks
+    callbacks: Cell<u32>,
+}
+
 /// The possible states
 #[derive(Copy, Clone, PartialEq)]
 enum Status {
@@ -117,8 +236,42 @@ enum Status {
     ExecutesPrint,
 }
 
+/// A client notified of every character that the driver displays
+///
+/// The client can give an additional feedback for the characters,
+/// for instance a tone for each character.
+pub trait CharacterClient {
+    /// Called when the driver displays a character
+    fn character_displayed(&self, character: char);
+}
+
+/// The size of a user glyph in the buffer allowed by a process
+///
+/// Byte 0 is the character, bytes 1 to 4 are the glyph stored
+/// as a little endian `u32`, using the same bit order as the
+/// font glyphs.
+pub const USER_GLYPH_SIZE: usize = 5;
+
+/// The size of an animation frame in the buffer allowed by a process
+///
+/// A frame is stored as a little endian `u32`, using the same
+/// bit order as the font glyphs.
+pub const FRAME_SIZE: usize = 4;
+
+/// The data type that will be stored in each
+/// process' grant.
+#[derive(Default)]
+pub struct AppData {
+    /// The glyphs registered by the process
+    glyphs: ReadOnlyProcessBuffer,
+    /// The frames of the process' animation
+    frames: ReadOnlyProcessBuffer,
+}
+
 /// Structure representing the driver
-pub struct LedMatrixText<'a, L: Led, A: Alarm<'a>> {
+///
+/// The driver displays the characters using the glyphs of the font `F`.
+pub struct LedMatrixText<'a, L: Led, A: Alarm<'a>, F: Font5x5 = DefaultFont> {
     /// the a slice of Matrix LEDs
     /// LED 0 is upper left, LED 24 is lower right
     leds: &'a [&'a L],
@@ -130,6 +283,9 @@ pub struct LedMatrixText<'a, L: Led, A: Alarm<'a>> {
     /// will notify when a request is done.
     client: OptionalCell<&'a dyn TextScreenClient>,
 
+    /// An optional client that the driver notifies of every displayed character
+    character_client: OptionalCell<&'a dyn CharacterClient>,
+
     /// The driver's buffer
     buffer: TakeCell<'a, [u8]>,
 
@@ -140,6 +296,21 @@ pub struct LedMatrixText<'a, L: Led, A: Alarm<'a>> {
     /// The length of the text stored in the driver's buffer
     len: Cell<usize>,
 
+    /// The pool of buffers for the messages printed by kernel capsules
+    kernel_buffers: &'a KernelBufferPool,
+
+    /// The buffer that stores the message printed by a kernel capsule
+    kernel_buffer: TakeCell<'static, [u8]>,
+
+    /// The length of the kernel message
+    kernel_len: Cell<usize>,
+
+    /// The position within the kernel message that will be displayed next
+    kernel_position: Cell<usize>,
+
+    /// The priority of the kernel message
+    kernel_priority: Cell<Priority>,
+
     /// A temporary buffer received by the driver from the client.
     /// The driver has to copy the data from this buffer to
     /// its own buffer and return this to the client.
@@ -153,6 +324,102 @@ pub struct LedMatrixText<'a, L: Led, A: Alarm<'a>> {
     /// expressed in milliseconds delay between to letters or digits.
     speed: Cell<u32>,
 
+    /// The speed of the displayed message, 0 means the display speed
+    message_speed: Cell<u32>,
+
+    /// The speed that will be used for the next message printed by the
+    /// process that set it, the display speed is used if it is not set
+    next_speed: OptionalCell<(ProcessId, u32)>,
+
+    /// The number of times a character blinks while it is displayed
+    blink_rate: Cell<u32>,
+
+    /// The number of times the displayed character has blinked
+    blinks: Cell<u32>,
+
+    /// In word mode, the time in milliseconds of the pause between two words,
+    /// 0 means `WORD_PAUSE_FACTOR` times the display speed
+    word_pause: Cell<u32>,
+
+    /// The speed at which the speed ramp starts, 0 disables the ramp
+    ramp_start: Cell<u32>,
+
+    /// The speed at which the speed ramp ends, 0 disables the ramp
+    ramp_target: Cell<u32>,
+
+    /// The number of characters of the text displayed since it was printed,
+    /// including the displayed one, used by the speed ramp
+    characters_shown: Cell<usize>,
+
+    /// The time in milliseconds the matrix is blank between two characters,
+    /// so that repeated characters can be told apart
+    gap: Cell<u32>,
+
+    /// The display mode
+    mode: Cell<Mode>,
+
+    /// The character played in Morse mode, `None` between characters
+    morse: Cell<Option<MorseCharacter>>,
+
+    /// The process that paused the display, the displayed frame stays
+    /// on the matrix until the display resumes
+    paused: OptionalCell<ProcessId>,
+
+    /// The frame of the text that was displayed when the display was paused
+    held_frame: Cell<Option<HeldFrame>>,
+
+    /// The number of times the text is displayed, 0 means forever
+    repeat: Cell<usize>,
+
+    /// The number of times the text has been displayed completely
+    passes: Cell<usize>,
+
+    /// Stores if the text is displayed in reverse, starting with its last
+    /// character and scrolling from left to right
+    reversed: Cell<bool>,
+
+    /// Stores if the text bounces, it is displayed to its end and
+    /// then back to its start instead of starting again
+    bounce: Cell<bool>,
+
+    /// In bounce mode, stores if the text is displayed back to its start
+    backwards: Cell<bool>,
+
+    /// In the scroll modes, the glyph of the previous character that slides out
+    previous_glyph: Cell<u32>,
+
+    /// In the scroll modes, the number of steps the current character has slid in
+    scroll_step: Cell<usize>,
+
+    /// The attributes of the displayed message
+    attributes: Cell<usize>,
+
+    /// The attributes that will be used for the next printed message
+    next_attributes: Cell<usize>,
+
+    /// The font glyph of the displayed character, before the
+    /// attributes are applied
+    glyph: Cell<u32>,
+
+    /// The LEDs that are known to be dead, using the same
+    /// bit order as the font glyphs
+    dead_leds: Cell<u32>,
+
+    /// The glyph displayed for the characters that the font does not have
+    placeholder: Cell<u32>,
+
+    /// The table that replaces the Latin-1 characters that the font does not have
+    transliteration: OptionalCell<&'a Transliteration>,
+
+    /// The LED reserved as the secure indicator, if any
+    secure_indicator: OptionalCell<usize>,
+
+    /// Stores if the secure indicator is lit
+    secure_indicator_lit: Cell<bool>,
+
+    /// The phase of the displayed frame
+    phase: Cell<Phase>,
+
     /// The status of the driver.
     status: Cell<Status>,
 
@@ -161,6 +428,12 @@ pub struct LedMatrixText<'a, L: Led, A: Alarm<'a>> {
     ///   - disabled means that it does not display that text
     is_enabled: Cell<bool>,
 
+    /// The display statistics
+    statistics: Statistics,
+
+    /// The time (in ticks) when the deferred callback was scheduled
+    deferred_call_time: Cell<u32>,
+
     /// A reference to the kernel's deferred caller used to schedule
     /// deferred callbacks (software interrupts)
     deferred_caller: &'a DynamicDeferredCall,
@@ -168,34 +441,105 @@ pub struct LedMatrixText<'a, L: Led, A: Alarm<'a>> {
     /// The handle (position in the kernel's deferred callbacks array)
     /// to the driver's deferred callback function
     deferred_call_handle: OptionalCell<DeferredCallHandle>,
+
+    /// The process that printed the displayed text with the hexadecimal or
+    /// fixed point commands, the text printed through `TextScreen` has no owner
+    text_owner: OptionalCell<ProcessId>,
+
+    /// The process whose glyphs are used
+    glyphs_owner: OptionalCell<ProcessId>,
+
+    /// The process whose animation is played
+    animation_owner: OptionalCell<ProcessId>,
+
+    /// The index of the animation frame that is played next
+    animation_frame: Cell<usize>,
+
+    /// The grant entrypoint
+    ///
+    /// The data type stored by the grant is `AppData` and
+    /// it can register up to 2 upcalls.
+    grant: Grant<AppData, 2>,
+
+    /// The font used to display the characters
+    font: PhantomData<F>,
 }
 
-impl<'a, L: Led, A: Alarm<'a>> LedMatrixText<'a, L, A> {
+impl<'a, L: Led, A: Alarm<'a>, F: Font5x5> LedMatrixText<'a, L, A, F> {
     /// Initializes a new driver structure
     pub fn new(
         leds: &'a [&'a L],
         alarm: &'a A,
-        buffer: &'a mut [u8],
+        text_buffers: &'a TextBufferPool,
+        kernel_buffers: &'a KernelBufferPool,
         speed: u32,
         deferred_caller: &'a DynamicDeferredCall,
+        grant: Grant<AppData, 2>,
     ) -> Self {
         if leds.len() != 25 {
             panic!("Expecting 25 LEDs, {} supplied", leds.len());
         }
+        let buffer = match text_buffers.take() {
+            Some(buffer) => buffer,
+            None => panic!("Expecting a buffer for the text, the pool is empty"),
+        };
         LedMatrixText {
             leds: leds,
             alarm: alarm,
             buffer: TakeCell::new(buffer),
+            kernel_buffers,
+            kernel_buffer: TakeCell::empty(),
+            kernel_len: Cell::new(0),
+            kernel_position: Cell::new(0),
+            kernel_priority: Cell::new(Priority::Info),
             client_buffer: TakeCell::empty(),
             client_len: Cell::new(0),
             position: Cell::new(0),
             speed: Cell::new(speed),
+            message_speed: Cell::new(0),
+            next_speed: OptionalCell::empty(),
+            blink_rate: Cell::new(1),
+            blinks: Cell::new(0),
+            word_pause: Cell::new(0),
+            ramp_start: Cell::new(0),
+            ramp_target: Cell::new(0),
+            characters_shown: Cell::new(0),
+            gap: Cell::new(0),
+            mode: Cell::new(Mode::Continuous),
+            morse: Cell::new(None),
+            paused: OptionalCell::empty(),
+            held_frame: Cell::new(None),
+            repeat: Cell::new(0),
+            passes: Cell::new(0),
+            reversed: Cell::new(false),
+            bounce: Cell::new(false),
+            backwards: Cell::new(false),
+            previous_glyph: Cell::new(0),
+            scroll_step: Cell::new(SCROLL_STEPS),
+            attributes: Cell::new(0),
+            next_attributes: Cell::new(0),
+            glyph: Cell::new(0),
+            dead_leds: Cell::new(0),
+            placeholder: Cell::new(DEFAULT_PLACEHOLDER),
+            transliteration: OptionalCell::empty(),
+            secure_indicator: OptionalCell::empty(),
+            secure_indicator_lit: Cell::new(false),
+            phase: Cell::new(Phase::Visible),
             len: Cell::new(0),
             status: Cell::new(Status::Idle),
             is_enabled: Cell::new(false),
+            statistics: Statistics::default(),
+            deferred_call_time: Cell::new(0),
             deferred_caller: deferred_caller,
             deferred_call_handle: OptionalCell::empty(),
             client: OptionalCell::empty(),
+            character_client: OptionalCell::empty(),
+            text_owner: OptionalCell::empty(),
+            glyphs_owner: OptionalCell::empty(),
+            animation_owner: OptionalCell::empty(),
+            animation_frame: Cell::new(0),
+            grant,
+            font: PhantomData,
         }
     }
 
@@ -204,28 +548,587 @@ impl<'a, L: Led, A: Alarm<'a>> LedMatrixText<'a, L, A> {
         self.deferred_call_handle.replace(deferred_call_handle);
     }
 
+    /// Set the LEDs that are known to be dead
+    ///
+    /// The board sets these from its self test or configuration. A lit
+    /// pixel of a dead LED is moved to its closest working neighbor,
+    /// keeping the text legible on damaged hardware.
+    pub fn set_dead_leds(&self, dead_leds: u32) {
+        self.dead_leds.set(dead_leds & FULL_FRAME);
+    }
+
+    /// Set the glyph displayed for the characters that the font does not have
+    pub fn set_placeholder(&self, placeholder: u32) {
+        self.placeholder.set(placeholder & FULL_FRAME);
+    }
+
+    /// Set the client notified of every displayed character
+    pub fn set_character_client(&self, character_client: &'a dyn CharacterClient) {
+        self.character_client.set(character_client);
+    }
+
+    /// Set the transliteration table
+    ///
+    /// The Latin-1 characters (0xC0 to 0xFF) that the font does not have
+    /// are displayed as the characters from the table, so that localized
+    /// text does not turn into placeholders. Each board can use its own table.
+    pub fn set_transliteration(&self, transliteration: &'a Transliteration) {
+        self.transliteration.set(transliteration);
+    }
+
+    /// Returns the character that replaces a Latin-1 character
+    fn transliterate(&self, character: char) -> Option<char> {
+        match character as u32 {
+            0xc0..=0xff => self.transliteration.extract().and_then(|transliteration| {
+                match transliteration[character as usize - 0xc0] {
+                    0 => None,
+                    replacement => Some(replacement as char),
+                }
+            }),
+            _ => None,
+        }
+    }
+
+    /// Reserve an LED as the secure indicator
+    ///
+    /// The LED is masked out of every displayed frame, so the text
+    /// printed by the processes can never light it. The driver lights
+    /// it while it displays a kernel message, so that the user can tell
+    /// the kernel's messages from the text printed by the processes.
+    /// Other kernel capsules can light it using `set_secure_indicator_lit`.
+    ///
+    /// The board must not give the processes another driver that can
+    /// light the LED, such as the LED matrix driver.
+    pub fn set_secure_indicator(&self, led: usize) {
+        if led >= 25 {
+            panic!("The secure indicator LED {} is not part of the matrix", led);
+        }
+        self.secure_indicator.set(led);
+        self.show_secure_indicator();
+    }
+
+    /// Lights or turns off the secure indicator
+    ///
+    /// This function is not reachable from processes.
+    pub fn set_secure_indicator_lit(&self, lit: bool) {
+        self.secure_indicator_lit.set(lit);
+        self.show_secure_indicator();
+    }
+
+    /// Returns the secure indicator's LED using the same
+    /// bit order as the font glyphs
+    fn secure_indicator_mask(&self) -> u32 {
+        self.secure_indicator.map_or(0, |led| 1 << (24 - *led))
+    }
+
+    /// Sets the secure indicator's LED on or off
+    fn show_secure_indicator(&self) {
+        self.secure_indicator.map(|led| {
+            if self.secure_indicator_lit.get() {
+                self.leds[*led].on();
+            } else {
+                self.leds[*led].off();
+            }
+        });
+    }
+
+    /// Prints a message from a kernel capsule
+    ///
+    /// The message is displayed once, before the text printed by the
+    /// processes continues. A message replaces the kernel message that
+    /// is displayed only if its priority is at least the same.
+    pub fn print_str(&self, text: &str, priority: Priority) -> Result<(), ErrorCode> {
+        let displays_kernel_message = self.kernel_position.get() < self.kernel_len.get();
+        if displays_kernel_message && priority < self.kernel_priority.get() {
+            self.increment(&self.statistics.dropped);
+            return Err(ErrorCode::BUSY);
+        }
+        let text = text.as_bytes();
+        let buffer = match self.kernel_buffers.take() {
+            Some(buffer) => buffer,
+            None => {
+                self.increment(&self.statistics.dropped);
+                return Err(ErrorCode::NOMEM);
+            }
+        };
+        if text.len() > buffer.len() {
+            let _ = self.kernel_buffers.replace(buffer);
+            self.increment(&self.statistics.dropped);
+            return Err(ErrorCode::SIZE);
+        }
+        buffer[..text.len()].copy_from_slice(text);
+        // Return the buffer of the replaced message to the pool.
+        if let Some(previous) = self.kernel_buffer.replace(buffer) {
+            let _ = self.kernel_buffers.replace(previous);
+        }
+        self.kernel_len.set(text.len());
+        self.kernel_position.set(0);
+        self.kernel_priority.set(priority);
+        // If nothing was displayed, the driver's alarm is disabled, so we
+        // start displaying the message. Kernel messages are displayed
+        // during a pause, but wait for the end of an animation.
+        if !displays_kernel_message
+            && (self.len.get() == 0 || self.is_paused())
+            && self.animation_owner.is_none()
+        {
+            self.display_next();
+        }
+        Ok(())
+    }
+
+    /// Replaces the displayed text with a text formatted by the driver
+    fn replace_text(&self, process_id: ProcessId, text: &[u8]) -> Result<(), ErrorCode> {
+        // Verify that we do no have another action in progress.
+        if self.status.get() != Status::Idle {
+            self.increment(&self.statistics.dropped);
+            return Err(ErrorCode::BUSY);
+        }
+        if self.get_buffer_len() < text.len() {
+            self.increment(&self.statistics.dropped);
+            return Err(ErrorCode::SIZE);
+        }
+        let previous_len = self.len.get();
+        // A message that is displayed is overwritten by the new one.
+        if previous_len > 0 {
+            self.increment(&self.statistics.overwritten);
+        }
+        // The attributes set for the next message apply from now on.
+        self.attributes.set(self.next_attributes.get());
+        self.message_speed
+            .set(self.take_next_speed(Some(process_id)));
+        self.buffer
+            .map(|buffer| buffer[..text.len()].copy_from_slice(text));
+        self.len.set(text.len());
+        self.text_owner.set(process_id);
+        self.position.set(0);
+        self.backwards.set(false);
+        self.passes.set(0);
+        self.characters_shown.set(0);
+        // If nothing was displayed, the driver's alarm is disabled,
+        // so we start displaying the text. The text waits for the
+        // end of an animation.
+        if previous_len == 0 && text.len() > 0 && self.animation_owner.is_none() {
+            self.display_next();
+        }
+        Ok(())
+    }
+
+    /// Replaces the displayed text with the hexadecimal representation of a value
+    ///
+    /// The value is displayed as 8 hexadecimal digits, so that processes
+    /// can show register values without formatting them.
+    fn print_hex(&self, process_id: ProcessId, value: u32) -> Result<(), ErrorCode> {
+        let mut text = [0; 8];
+        // Write the digits starting with the most significant one.
+        for (position, character) in text.iter_mut().enumerate() {
+            let digit = (value >> (28 - 4 * position)) & 0x0f;
+            *character = b"0123456789ABCDEF"[digit as usize];
+        }
+        self.replace_text(process_id, &text)
+    }
+
+    /// Replaces the displayed text with a fixed point value
+    ///
+    /// The value is `value / 10^decimals`, for instance a temperature
+    /// of 235 with 1 decimal is displayed as 23.5.
+    fn print_fixed_point(
+        &self,
+        process_id: ProcessId,
+        value: i32,
+        decimals: usize,
+    ) -> Result<(), ErrorCode> {
+        if decimals > MAX_DECIMALS {
+            return Err(ErrorCode::INVAL);
+        }
+        // The sign, 10 digits and the decimal point
+        let mut text = [0; 12];
+        let mut len = 0;
+        let mut magnitude = value.unsigned_abs();
+        let mut digits = 0;
+        // Write the digits starting with the least significant one, with
+        // at least one digit before the decimal point.
+        while magnitude > 0 || digits <= decimals {
+            if digits == decimals && decimals > 0 {
+                text[len] = b'.';
+                len += 1;
+            }
+            text[len] = b'0' + (magnitude % 10) as u8;
+            len += 1;
+            magnitude /= 10;
+            digits += 1;
+        }
+        if value < 0 {
+            text[len] = b'-';
+            len += 1;
+        }
+        text[..len].reverse();
+        self.replace_text(process_id, &text[..len])
+    }
+
+    /// Displays the next letter or digit of the kernel message
+    ///
+    /// Returns `false` if there is no kernel message to display.
+    fn display_next_kernel(&self) -> bool {
+        if self.kernel_position.get() < self.kernel_len.get() {
+            let character = self.kernel_buffer.map_or(' ', |buffer| {
+                buffer
+                    .get(self.kernel_position.get())
+                    .map_or(' ', |c| *c as char)
+            });
+            // The kernel owns the display until the end of the message.
+            if !self.secure_indicator_lit.get() {
+                self.set_secure_indicator_lit(true);
+            }
+            let _ = self.display(character);
+            self.kernel_position.set(self.kernel_position.get() + 1);
+            self.alarm.set_alarm(
+                self.alarm.now(),
+                self.alarm.ticks_from_ms(self.visible_time()),
+            );
+            true
+        } else {
+            // The kernel message has been displayed.
+            if let Some(buffer) = self.kernel_buffer.take() {
+                let _ = self.kernel_buffers.replace(buffer);
+            }
+            self.kernel_buffers.check_leaks(0);
+            self.kernel_len.set(0);
+            self.kernel_position.set(0);
+            if self.secure_indicator_lit.get() {
+                self.set_secure_indicator_lit(false);
+            }
+            false
+        }
+    }
+
     /// schedule a deferred callback (sfotware interrupt)
     fn schedule_deferred_callback(&self) {
+        self.deferred_call_time.set(self.alarm.now().into_u32());
         self.deferred_call_handle
             .map(|handle| self.deferred_caller.set(*handle));
     }
 
+    /// Returns the speed of the message printed by a process, 0 means
+    /// the display speed
+    ///
+    /// The speed set for the next message applies only to the next message
+    /// of the process that set it. It stays in place when another process,
+    /// or an unknown one, prints a message.
+    fn take_next_speed(&self, process_id: Option<ProcessId>) -> u32 {
+        let set_by_printer = self.next_speed.map_or(false, |(owner, _)| {
+            process_id.map_or(false, |process_id| *owner == process_id)
+        });
+        if set_by_printer {
+            self.next_speed.take().map_or(0, |(_, speed)| speed)
+        } else {
+            0
+        }
+    }
+
+    /// Sets the speed of the next printed message, 0 means the display speed
+    ///
+    /// The speed belongs to the process that set it until the message is
+    /// printed, the other processes cannot change it meanwhile.
+    fn set_next_speed(&self, process_id: ProcessId, speed: u32) -> Result<(), ErrorCode> {
+        let set_by_other = self.next_speed.map_or(false, |(owner, _)| {
+            *owner != process_id && self.exists(*owner)
+        });
+        if set_by_other {
+            return Err(ErrorCode::BUSY);
+        }
+        if speed == 0 {
+            self.next_speed.clear();
+        } else {
+            self.next_speed.set((process_id, speed));
+        }
+        Ok(())
+    }
+
+    /// Verifies if a process still exists
+    ///
+    /// The grant of a process is removed when the process exits,
+    /// is restarted or faults.
+    fn exists(&self, process_id: ProcessId) -> bool {
+        self.grant.enter(process_id, |_, _| {}).is_ok()
+    }
+
+    /// Forgets the processes that no longer exist
+    ///
+    /// The kernel does not notify the drivers when a process exits, so the
+    /// driver verifies the owners of the display's content before using them.
+    /// The text printed by a process that exited is removed and the matrix is
+    /// cleared if it was showing that text.
+    fn release_exited(&self) {
+        if self
+            .glyphs_owner
+            .map_or(false, |owner| !self.exists(*owner))
+        {
+            self.glyphs_owner.clear();
+        }
+        if self
+            .next_speed
+            .map_or(false, |(owner, _)| !self.exists(*owner))
+        {
+            self.next_speed.clear();
+        }
+        // The text continues when the alarm of the next frame expires.
+        if self
+            .animation_owner
+            .map_or(false, |owner| !self.exists(*owner))
+        {
+            self.animation_owner.clear();
+        }
+        if self.text_owner.map_or(false, |owner| !self.exists(*owner)) {
+            self.text_owner.clear();
+            self.message_speed.set(0);
+            self.len.set(0);
+            self.position.set(0);
+            self.backwards.set(false);
+            self.passes.set(0);
+            // Kernel messages and animations are not the text of the process.
+            if self.kernel_len.get() == 0 && self.animation_owner.is_none() {
+                self.clear();
+            }
+        }
+        // The display resumes if the process that paused it exited.
+        self.is_paused();
+    }
+
+    /// Verifies if the display is paused
+    ///
+    /// The display resumes when the process that paused it no longer exists.
+    /// This is verified when the display is used, as the alarm is stopped
+    /// during the pause.
+    fn is_paused(&self) -> bool {
+        match self.paused.extract() {
+            Some(process_id) => {
+                if self.exists(process_id) {
+                    true
+                } else {
+                    self.continue_after_pause();
+                    false
+                }
+            }
+            None => false,
+        }
+    }
+
+    /// Pauses the display
+    ///
+    /// The alarm is stopped, so the displayed frame stays on the matrix.
+    /// Only the process that paused the display can pause it again.
+    fn pause(&self, process_id: ProcessId) -> Result<(), ErrorCode> {
+        if self.is_paused() && !self.paused.contains(&process_id) {
+            return Err(ErrorCode::BUSY);
+        }
+        if self.paused.is_none() {
+            // The kernel messages continue during the pause.
+            if self.kernel_len.get() == 0 {
+                let _ = self.alarm.disarm();
+                self.held_frame.set(Some(HeldFrame {
+                    glyph: self.glyph.get(),
+                    previous_glyph: self.previous_glyph.get(),
+                    scroll_step: self.scroll_step.get(),
+                    phase: self.phase.get(),
+                    morse: self.morse.get(),
+                }));
+            }
+            self.paused.set(process_id);
+        }
+        Ok(())
+    }
+
+    /// Resumes the display from the character where it was paused
+    ///
+    /// Only the process that paused the display can resume it.
+    fn resume(&self, process_id: ProcessId) -> Result<(), ErrorCode> {
+        if self.is_paused() && !self.paused.contains(&process_id) {
+            return Err(ErrorCode::BUSY);
+        }
+        if self.paused.is_some() {
+            self.continue_after_pause();
+        }
+        Ok(())
+    }
+
+    /// Ends the pause and sets the alarm that was stopped during the pause
+    fn continue_after_pause(&self) {
+        self.paused.clear();
+        self.held_frame.set(None);
+        if !self.alarm.is_armed() {
+            self.alarm.set_alarm(
+                self.alarm.now(),
+                self.alarm.ticks_from_ms(self.visible_time()),
+            );
+        }
+    }
+
+    /// Displays again the frame held by the pause, after a kernel message
+    fn show_held_frame(&self) {
+        match self.held_frame.get() {
+            Some(held) => {
+                self.glyph.set(held.glyph);
+                self.previous_glyph.set(held.previous_glyph);
+                self.scroll_step.set(held.scroll_step);
+                self.phase.set(held.phase);
+                self.morse.set(held.morse);
+                self.render();
+            }
+            // The display was paused during a kernel message, there
+            // is no frame of the text to display.
+            None => self.clear(),
+        }
+    }
+
+    /// Starts playing the frames allowed by a process
+    ///
+    /// The animation replaces the text until its last frame has been played.
+    fn play_animation(&self, process_id: ProcessId) -> Result<(), ErrorCode> {
+        if self
+            .animation_owner
+            .map_or(false, |owner| *owner != process_id)
+        {
+            return Err(ErrorCode::BUSY);
+        }
+        let frames = self
+            .grant
+            .enter(process_id, |app, _| app.frames.len() / FRAME_SIZE)
+            .map_err(ErrorCode::from)?;
+        if frames == 0 {
+            return Err(ErrorCode::INVAL);
+        }
+        self.animation_owner.set(process_id);
+        self.animation_frame.set(0);
+        // The frames are displayed as they are, without Morse code or scrolling.
+        self.morse.set(None);
+        self.scroll_step.set(SCROLL_STEPS);
+        self.phase.set(Phase::Visible);
+        self.play_frame();
+        Ok(())
+    }
+
+    /// Plays the next frame of the animation
+    ///
+    /// Returns `false` if the animation has ended, in which case the process
+    /// that played it is notified through upcall 1.
+    fn play_frame(&self) -> bool {
+        let index = self.animation_frame.get();
+        let frame = self.animation_owner.extract().and_then(|process_id| {
+            self.grant
+                .enter(process_id, |app, _| {
+                    app.frames
+                        .enter(|frames| {
+                            let start = index * FRAME_SIZE;
+                            if start + FRAME_SIZE <= frames.len() {
+                                Some(
+                                    u32::from_le_bytes([
+                                        frames[start].get(),
+                                        frames[start + 1].get(),
+                                        frames[start + 2].get(),
+                                        frames[start + 3].get(),
+                                    ]) & FULL_FRAME,
+                                )
+                            } else {
+                                None
+                            }
+                        })
+                        .unwrap_or(None)
+                })
+                .unwrap_or(None)
+        });
+        match frame {
+            Some(frame) => {
+                if self.is_enabled.get() {
+                    self.print(frame);
+                }
+                self.animation_frame.set(index + 1);
+                self.alarm.set_alarm(
+                    self.alarm.now(),
+                    self.alarm.ticks_from_ms(self.current_speed()),
+                );
+                true
+            }
+            None => {
+                if let Some(process_id) = self.animation_owner.take() {
+                    let _ = self.grant.enter(process_id, |_, upcalls| {
+                        let _ = upcalls.schedule_upcall(1, (0, 0, 0));
+                    });
+                }
+                false
+            }
+        }
+    }
+
+    /// Stops displaying the text after its last repeat
+    ///
+    /// The processes that subscribed to upcall 0 are notified, so that
+    /// they can display the next message.
+    fn finish(&self) {
+        self.len.set(0);
+        self.text_owner.clear();
+        self.passes.set(0);
+        self.clear();
+        self.grant.each(|_, _, upcalls| {
+            let _ = upcalls.schedule_upcall(0, (0, 0, 0));
+        });
+    }
+
     /// Displays the next letter or digit from the driver's buffer
     fn display_next(&self) {
+        // Kernel messages are displayed before the text of the processes.
+        if self.display_next_kernel() {
+            return;
+        }
+        // In word mode, marks that the displayed character ends a word.
+        let mut end_of_word = false;
         // Verify if we are at the end of the buffer.
         if self.position.get() >= self.len.get() {
-            // Reset the position to the start of the buffer.
-            self.position.set(0);
+            if self.bounce.get() && self.len.get() > 1 {
+                // Turn around, the character at the end is not displayed twice.
+                self.backwards.set(!self.backwards.get());
+                self.position.set(1);
+            } else {
+                // Reset the position to the start of the buffer.
+                self.position.set(0);
+            }
+            // The whole text has been displayed, in bounce mode forth and back.
+            if self.len.get() > 0 && !self.backwards.get() {
+                self.increment(&self.statistics.completed);
+                self.passes.set(self.passes.get() + 1);
+                // The text has been displayed the requested number of times.
+                if self.repeat.get() != 0 && self.passes.get() >= self.repeat.get() {
+                    self.finish();
+                    return;
+                }
+            }
+            // In word mode, the end of the text also ends the last word.
+            end_of_word = self.mode.get() == Mode::Words;
         }
-        // Verify if the current position is within the length
-        // of the text. This can be false only if the length
-        // of the text is 0.
-        if self.position.get() < self.len.get() {
+        if end_of_word {
+            // Pause before displaying the text again.
+            let _ = self.display(' ');
+        } else if self.position.get() < self.len.get() {
+            // Verify if the current position is within the length
+            // of the text. This can be false only if the length
+            // of the text is 0.
             if !self.buffer.map_or(false, |buffer| {
                 // Make sure we are within the buffers length
                 if self.position.get() < buffer.len() {
+                    let character = buffer[self.text_index(self.position.get())] as char;
+                    if self.mode.get() == Mode::Words && character == ' ' {
+                        // A space ends a word, the following spaces
+                        // are part of the same pause.
+                        while self.position.get() + 1 < self.len.get()
+                            && buffer[self.text_index(self.position.get() + 1)] == b' '
+                        {
+                            self.position.set(self.position.get() + 1);
+                        }
+                        end_of_word = true;
+                    }
                     // Display the letter or digit.
-                    let _ = self.display(buffer[self.position.get()] as char);
+                    self.characters_shown
+                        .set(self.characters_shown.get().saturating_add(1));
+                    let _ = self.display(character);
                     // We successfully displayed a letter or a digit,
                     // so we increase the current position
                     self.position.set(self.position.get() + 1);
@@ -253,23 +1156,346 @@ impl<'a, L: Led, A: Alarm<'a>> LedMatrixText<'a, L, A> {
         // Not setting the alarm allows the MCU to enter low power
         // modes (if there are no other taks pending).
         if self.len.get() > 0 {
+            let delay = if self.mode.get() == Mode::Morse {
+                // The Morse character starts after a short pause, spaces
+                // and unknown characters are pauses between words.
+                if self.morse.get().is_some() {
+                    self.morse_unit()
+                } else {
+                    self.morse_unit() * MORSE_WORD_UNITS
+                }
+            } else if end_of_word {
+                self.word_pause()
+            } else {
+                self.visible_time()
+            };
             self.alarm
-                .set_alarm(self.alarm.now(), self.alarm.ticks_from_ms(self.speed.get()));
+                .set_alarm(self.alarm.now(), self.alarm.ticks_from_ms(delay));
+        }
+    }
+
+    /// Increments a statistics counter
+    fn increment(&self, counter: &Cell<u32>) {
+        counter.set(counter.get().wrapping_add(1));
+    }
+
+    /// Returns a statistics value
+    ///
+    /// The average callback latency is expressed in microseconds.
+    fn statistic(&self, statistic: usize) -> Option<u32> {
+        match statistic {
+            0 => Some(self.statistics.frames.get()),
+            1 => Some(self.statistics.completed.get()),
+            2 => Some(self.statistics.overwritten.get()),
+            3 => Some(self.statistics.dropped.get()),
+            // The driver stores a single message, so the queue depth is 0 or 1.
+            4 => Some(if self.len.get() > 0 { 1 } else { 0 }),
+            5 => Some(
+                self.statistics
+                    .latency
+                    .get()
+                    .checked_div(self.statistics.callbacks.get())
+                    .map_or(0, |latency| self.alarm.ticks_to_us(A::Ticks::from(latency))),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Resets all the statistics
+    fn reset_statistics(&self) {
+        self.statistics.frames.set(0);
+        self.statistics.completed.set(0);
+        self.statistics.overwritten.set(0);
+        self.statistics.dropped.set(0);
+        self.statistics.latency.set(0);
+        self.statistics.callbacks.set(0);
+    }
+
+    /// Verifies if the displayed message has an attribute
+    fn has_attribute(&self, attribute: usize) -> bool {
+        self.attributes.get() & attribute != 0
+    }
+
+    /// Returns the time in milliseconds that a character is displayed
+    fn character_time(&self) -> u32 {
+        let speed = self.current_speed();
+        match self.mode.get() {
+            Mode::Continuous => speed,
+            Mode::Words => speed / WORD_MODE_SPEEDUP,
+            Mode::Morse => speed,
+            Mode::Scroll | Mode::ScrollUp | Mode::ScrollDown => speed,
+            Mode::Wipe | Mode::Dissolve => speed,
+        }
+    }
+
+    /// Returns the speed at which the current character is displayed
+    ///
+    /// The speed printed with the message, if any, takes precedence as it
+    /// applies only to that message. Otherwise, with a speed ramp, the speed
+    /// moves from the start speed to the target speed during the first
+    /// `RAMP_CHARACTERS` characters of the text, the first character is
+    /// displayed at the start speed. Otherwise, this is the display speed.
+    fn current_speed(&self) -> u32 {
+        if self.message_speed.get() != 0 {
+            return self.message_speed.get();
+        }
+        let (start, target) = (self.ramp_start.get(), self.ramp_target.get());
+        if start == 0 || target == 0 {
+            return self.speed.get();
+        }
+        // The ramp follows the displayed characters, so it does not start
+        // again when the text bounces or repeats. The multiplication is
+        // done on 64 bits so that it does not overflow.
+        let shown = self.characters_shown.get().saturating_sub(1);
+        let characters = cmp::min(shown, RAMP_CHARACTERS) as u64;
+        if target >= start {
+            start + ((target - start) as u64 * characters / RAMP_CHARACTERS as u64) as u32
+        } else {
+            start - ((start - target) as u64 * characters / RAMP_CHARACTERS as u64) as u32
+        }
+    }
+
+    /// Returns the time in milliseconds of the pause between two words
+    fn word_pause(&self) -> u32 {
+        match self.word_pause.get() {
+            0 => self.current_speed().saturating_mul(WORD_PAUSE_FACTOR),
+            pause => pause,
+        }
+    }
+
+    /// Returns the duration in milliseconds of a Morse dot
+    fn morse_unit(&self) -> u32 {
+        cmp::max(self.current_speed() / MORSE_UNIT_DIVIDER, 1)
+    }
+
+    /// Returns the Morse code of a character
+    fn morse_code(character: char) -> Option<&'static str> {
+        // Morse code does not distinguish lowercase letters.
+        let character = character.to_ascii_uppercase();
+        match character {
+            'A'..='Z' => Some(MORSE_LETTERS[character as usize - 'A' as usize]),
+            '0'..='9' => Some(MORSE_DIGITS[character as usize - '0' as usize]),
+            _ => None,
+        }
+    }
+
+    /// Plays the next step of the Morse character, lighting the whole
+    /// matrix for a dot or a dash or turning it off for a pause
+    ///
+    /// Returns `false` if the character has been played.
+    fn play_morse(&self) -> bool {
+        match self.morse.get() {
+            Some(mut character) => {
+                let units = if self.phase.get() == Phase::Morse {
+                    // The dot or dash has been played, pause before the next one.
+                    self.phase.set(Phase::Hidden);
+                    if character.position == character.code.len() {
+                        // The character has been played, pause before the next character.
+                        self.morse.set(None);
+                        MORSE_DASH_UNITS
+                    } else {
+                        1
+                    }
+                } else {
+                    // Play the next dot or dash.
+                    let dash = character.code.as_bytes()[character.position] == b'-';
+                    character.position += 1;
+                    self.morse.set(Some(character));
+                    self.phase.set(Phase::Morse);
+                    if dash {
+                        MORSE_DASH_UNITS
+                    } else {
+                        1
+                    }
+                };
+                self.render();
+                self.alarm.set_alarm(
+                    self.alarm.now(),
+                    self.alarm.ticks_from_ms(self.morse_unit() * units),
+                );
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the time in milliseconds that a character is visible
+    ///
+    /// If the message blinks, each character is visible only
+    /// for half of each blink.
+    ///
+    /// In the scroll modes, this is the time of one step.
+    fn visible_time(&self) -> u32 {
+        if self.is_scrolling() {
+            self.character_time() / SCROLL_STEPS as u32
+        } else if self.has_attribute(ATTRIBUTE_BLINK) {
+            self.blink_period() / 2
+        } else {
+            self.character_time()
         }
     }
 
+    /// Returns the time in milliseconds of one blink, the
+    /// character is visible for half of it
+    ///
+    /// A blink lasts at least 1 ms, even if the display speed
+    /// has changed after the blink rate was set.
+    fn blink_period(&self) -> u32 {
+        cmp::max(self.character_time() / self.blink_rate.get(), 1)
+    }
+
     /// Prints the a font `glyph` by setting LEDs
     /// on and off depending on the glyph's bits
     ///
     /// A font glyph is a set of bits that represents that
     /// state of the LEDs
     fn print(&self, glyph: u32) {
+        self.glyph.set(glyph);
+        self.render();
+    }
+
+    /// Returns the index in the driver's buffer of the character
+    /// displayed at `position`
+    fn text_index(&self, position: usize) -> usize {
+        if self.reversed.get() != self.backwards.get() {
+            self.len.get() - 1 - position
+        } else {
+            position
+        }
+    }
+
+    /// Verifies if the text scrolls smoothly or changes with a transition
+    fn is_scrolling(&self) -> bool {
+        matches!(
+            self.mode.get(),
+            Mode::Scroll | Mode::ScrollUp | Mode::ScrollDown | Mode::Wipe | Mode::Dissolve
+        )
+    }
+
+    /// Returns the glyph that is displayed
+    ///
+    /// In the scroll modes, this is a window over the previous and the
+    /// current glyph, the window moves one column or row at each step.
+    /// In the transition modes, the LEDs selected by the step show the
+    /// current glyph and the others show the previous glyph.
+    fn compose(&self) -> u32 {
+        let glyph = self.glyph.get();
+        let previous = self.previous_glyph.get();
+        let step = self.scroll_step.get();
+        match self.mode.get() {
+            Mode::Scroll => {
+                (0..5).fold(0, |frame, row| {
+                    let shift = 5 * (4 - row);
+                    let (previous, glyph) = ((previous >> shift) & 0x1f, (glyph >> shift) & 0x1f);
+                    // The row of the previous glyph, an empty column and the row of the
+                    // current glyph, in reverse (or back in bounce mode) the current
+                    // glyph slides in from the left.
+                    let (strip, offset) = if self.reversed.get() != self.backwards.get() {
+                        ((glyph << 6) | previous, step)
+                    } else {
+                        ((previous << 6) | glyph, SCROLL_STEPS - step)
+                    };
+                    frame | (((strip >> offset) & 0x1f) << shift)
+                })
+            }
+            Mode::ScrollUp => {
+                // The previous glyph, an empty row and the current glyph
+                let strip = ((previous as u64) << 30) | glyph as u64;
+                (strip >> (5 * (SCROLL_STEPS - step))) as u32 & FULL_FRAME
+            }
+            Mode::ScrollDown => {
+                // The current glyph, an empty row and the previous glyph
+                let strip = ((glyph as u64) << 30) | previous as u64;
+                (strip >> (5 * step)) as u32 & FULL_FRAME
+            }
+            Mode::Wipe => {
+                // The first columns of every row
+                let columns = 5 * step / SCROLL_STEPS;
+                let mask = ((0x1f << (5 - columns)) & 0x1f) * FIRST_COLUMN;
+                (glyph & mask) | (previous & !mask & FULL_FRAME)
+            }
+            Mode::Dissolve => {
+                let leds = 25 * step / SCROLL_STEPS;
+                let mask = (0..leds).fold(0, |mask, index| {
+                    mask | (1 << ((index * DISSOLVE_STRIDE) % 25))
+                });
+                (glyph & mask) | (previous & !mask & FULL_FRAME)
+            }
+            _ => glyph,
+        }
+    }
+
+    /// Moves the current character one step further in
+    ///
+    /// Returns `false` if the character has completely slid in.
+    fn scroll(&self) -> bool {
+        let step = self.scroll_step.get();
+        if step < SCROLL_STEPS && self.phase.get() == Phase::Visible {
+            self.scroll_step.set(step + 1);
+            self.render();
+            self.alarm.set_alarm(
+                self.alarm.now(),
+                self.alarm.ticks_from_ms(self.visible_time()),
+            );
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Applies the attributes and the phase to the stored glyph and
+    /// sets the LEDs on and off depending on the resulting frame's bits
+    fn render(&self) {
+        let frame = match self.phase.get() {
+            Phase::Visible => {
+                if self.has_attribute(ATTRIBUTE_INVERSE) {
+                    !self.compose() & FULL_FRAME
+                } else {
+                    self.compose()
+                }
+            }
+            Phase::Hidden | Phase::Gap => 0,
+            Phase::Flash | Phase::Morse => FULL_FRAME,
+        };
+        // The secure indicator is never part of the displayed text.
+        let frame = self.remap(frame) & !self.secure_indicator_mask();
+        self.increment(&self.statistics.frames);
         for index in 0..25 {
-            match (glyph >> (24 - index)) & 0x01 {
+            match (frame >> (24 - index)) & 0x01 {
                 0 => self.leds[index].off(),
                 _ => self.leds[index].on(),
             }
         }
+        self.show_secure_indicator();
+    }
+
+    /// Moves the lit pixels of the dead LEDs to their closest working
+    /// neighbors (right, left, below, above) that are not already lit
+    fn remap(&self, frame: u32) -> u32 {
+        let dead_leds = self.dead_leds.get();
+        let mut remapped = frame & !dead_leds;
+        for index in 0..25usize {
+            if ((frame & dead_leds) >> (24 - index)) & 0x01 == 1 {
+                let (row, column) = (index / 5, index % 5);
+                let neighbors = [
+                    (row, column + 1),
+                    (row, column.wrapping_sub(1)),
+                    (row + 1, column),
+                    (row.wrapping_sub(1), column),
+                ];
+                // Pixels that are lit, dead or reserved cannot be used as a substitute.
+                let used = frame | remapped | dead_leds | self.secure_indicator_mask();
+                if let Some(neighbor) = neighbors
+                    .iter()
+                    .filter(|(row, column)| *row < 5 && *column < 5)
+                    .map(|(row, column)| row * 5 + column)
+                    .find(|neighbor| (used >> (24 - neighbor)) & 0x01 == 0)
+                {
+                    remapped |= 1 << (24 - neighbor);
+                }
+            }
+        }
+        remapped
     }
 
     /// Clears the displayed glyph by turning off
@@ -278,24 +1504,84 @@ impl<'a, L: Led, A: Alarm<'a>> LedMatrixText<'a, L, A> {
         for index in 0..25 {
             self.leds[index].off();
         }
+        self.show_secure_indicator();
+    }
+
+    /// Returns the glyph registered by a process for a character
+    fn user_glyph(&self, character: char) -> Option<u32> {
+        if character as u32 > 0xff {
+            return None;
+        }
+        self.glyphs_owner.extract().and_then(|process_id| {
+            self.grant
+                .enter(process_id, |app, _| {
+                    app.glyphs
+                        .enter(|glyphs| {
+                            (0..glyphs.len() / USER_GLYPH_SIZE)
+                                .map(|index| index * USER_GLYPH_SIZE)
+                                .find(|start| glyphs[*start].get() == character as u8)
+                                .map(|start| {
+                                    u32::from_le_bytes([
+                                        glyphs[start + 1].get(),
+                                        glyphs[start + 2].get(),
+                                        glyphs[start + 3].get(),
+                                        glyphs[start + 4].get(),
+                                    ]) & FULL_FRAME
+                                })
+                        })
+                        .unwrap_or(None)
+                })
+                .unwrap_or(None)
+        })
     }
 
     /// Displays a character
     fn display(&self, character: char) -> Result<(), ErrorCode> {
         if self.is_enabled.get() {
-            let displayed_character = character.to_ascii_uppercase();
-            match displayed_character {
-                '0'..='9' => {
-                    self.print(DIGITS[displayed_character as usize - '0' as usize]);
+            self.character_client
+                .map(|client| client.character_displayed(character));
+            if self.is_scrolling() {
+                // The previous character slides out while this one slides in.
+                self.previous_glyph.set(self.glyph.get());
+                self.scroll_step.set(1);
+            }
+            if self.mode.get() == Mode::Morse {
+                // The character is played by the next alarms,
+                // the matrix is off until then.
+                let code = Self::morse_code(character).or_else(|| {
+                    self.transliterate(character)
+                        .and_then(|replacement| Self::morse_code(replacement))
+                });
+                self.morse
+                    .set(code.map(|code| MorseCharacter { code, position: 0 }));
+                self.clear();
+                return if code.is_some() || character == ' ' {
                     Ok(())
-                }
-                'A'..='Z' => {
-                    self.print(LETTERS[displayed_character as usize - 'A' as usize]);
+                } else {
+                    Err(ErrorCode::INVAL)
+                };
+            }
+            // The glyphs registered by a process take
+            // precedence over the font's glyphs.
+            if let Some(glyph) = self.user_glyph(character) {
+                self.print(glyph);
+                return Ok(());
+            }
+            // The characters that the font does not have may be transliterated.
+            let glyph = F::glyph(character).or_else(|| {
+                self.transliterate(character)
+                    .and_then(|replacement| F::glyph(replacement))
+            });
+            match glyph {
+                Some(glyph) => {
+                    self.print(glyph);
                     Ok(())
                 }
-                _ => {
-                    self.clear();
-                    Err(ErrorCode::INVAL)
+                None => {
+                    // The placeholder replaces the unknown character
+                    // and the text continues to scroll.
+                    self.print(self.placeholder.get());
+                    Ok(())
                 }
             }
         } else {
@@ -311,19 +1597,100 @@ impl<'a, L: Led, A: Alarm<'a>> LedMatrixText<'a, L, A> {
 }
 
 /// This implementation allows `LedMatrixText` to use an alarm.
-impl<'a, L: Led, A: Alarm<'a>> AlarmClient for LedMatrixText<'a, L, A> {
+impl<'a, L: Led, A: Alarm<'a>, F: Font5x5> AlarmClient for LedMatrixText<'a, L, A, F> {
     /// Called when the alarm expires
     fn alarm(&self) {
-        // The alarm has expired, the current letter or digit has been displayed enugh,
-        // display the next letter or digit
-        self.display_next();
+        self.release_exited();
+        if self.is_paused() {
+            let displays_kernel_message = self.kernel_len.get() > 0;
+            // Kernel messages are displayed during the pause, the
+            // alarm is not set again once they have been displayed.
+            if !self.display_next_kernel() && displays_kernel_message {
+                self.show_held_frame();
+            }
+            return;
+        }
+        if self.animation_owner.is_some() {
+            if !self.play_frame() {
+                // The animation has ended, the text continues.
+                self.phase.set(Phase::Visible);
+                self.blinks.set(0);
+                self.display_next();
+            }
+            // The animation replaces the text until it ends.
+            return;
+        }
+        if self.mode.get() == Mode::Morse && self.play_morse() {
+            // The dots and dashes of the Morse character are played first.
+            return;
+        }
+        if self.is_scrolling() && self.scroll() {
+            // The character slides in before it is replaced.
+            return;
+        }
+        if self.phase.get() == Phase::Visible && self.has_attribute(ATTRIBUTE_BLINK) {
+            // The current letter or digit has been visible for half of the blink,
+            // hide it for the other half.
+            self.phase.set(Phase::Hidden);
+            self.render();
+            self.alarm.set_alarm(
+                self.alarm.now(),
+                self.alarm.ticks_from_ms(cmp::max(
+                    self.blink_period().saturating_sub(self.visible_time()),
+                    1,
+                )),
+            );
+        } else if self.phase.get() == Phase::Hidden
+            && self.has_attribute(ATTRIBUTE_BLINK)
+            && self.blinks.get() + 1 < self.blink_rate.get()
+        {
+            // The current letter or digit blinks again.
+            self.blinks.set(self.blinks.get() + 1);
+            self.phase.set(Phase::Visible);
+            self.render();
+            self.alarm.set_alarm(
+                self.alarm.now(),
+                self.alarm.ticks_from_ms(self.visible_time()),
+            );
+        } else if matches!(self.phase.get(), Phase::Visible | Phase::Hidden)
+            && self.gap.get() > 0
+            && self.mode.get() != Mode::Morse
+            && !self.is_scrolling()
+        {
+            // Blank the matrix before the next letter or digit. The Morse
+            // and scroll modes have their own pauses between characters.
+            self.phase.set(Phase::Gap);
+            self.render();
+            self.alarm
+                .set_alarm(self.alarm.now(), self.alarm.ticks_from_ms(self.gap.get()));
+        } else {
+            // The alarm has expired, the current letter or digit has been displayed enugh,
+            // display the next letter or digit
+            self.phase.set(Phase::Visible);
+            self.blinks.set(0);
+            self.display_next();
+        }
     }
 }
 
 /// This implementation allows `LedMatrixText` to receive deferred callbacks (software interrupts)
-impl<'a, L: Led, A: Alarm<'a>> DynamicDeferredCallClient for LedMatrixText<'a, L, A> {
+impl<'a, L: Led, A: Alarm<'a>, F: Font5x5> DynamicDeferredCallClient
+    for LedMatrixText<'a, L, A, F>
+{
     /// The deferred callback (software interrupt) handler
     fn call(&self, _handle: DeferredCallHandle) {
+        // Measure how long it took the kernel to call us.
+        let latency = self
+            .alarm
+            .now()
+            .wrapping_sub(A::Ticks::from(self.deferred_call_time.get()));
+        self.statistics.latency.set(
+            self.statistics
+                .latency
+                .get()
+                .wrapping_add(latency.into_u32()),
+        );
+        self.increment(&self.statistics.callbacks);
         match self.status.get() {
             // We should not get here, we ignore it.
             Status::Idle => {}
@@ -349,7 +1716,7 @@ impl<'a, L: Led, A: Alarm<'a>> DynamicDeferredCallClient for LedMatrixText<'a, L
 }
 
 /// This implementation allows `LedMatrixText` to be used as a service driver to `TextSceen`.
-impl<'a, L: Led, A: Alarm<'a>> TextScreen<'a> for LedMatrixText<'a, L, A> {
+impl<'a, L: Led, A: Alarm<'a>, F: Font5x5> TextScreen<'a> for LedMatrixText<'a, L, A, F> {
     fn set_client(&self, client: Option<&'a dyn TextScreenClient>) {
         if let Some(client) = client {
             self.client.set(client);
@@ -378,6 +1745,15 @@ impl<'a, L: Led, A: Alarm<'a>> TextScreen<'a> for LedMatrixText<'a, L, A> {
                 self.status.set(Status::ExecutesPrint);
                 // Store the previous length of the text we store in the driver's buffer.
                 let previous_len = self.len.get();
+                // A message that is displayed is overwritten by the new one.
+                if previous_len > 0 {
+                    self.increment(&self.statistics.overwritten);
+                }
+                // The attributes set for the next message apply from now on.
+                self.attributes.set(self.next_attributes.get());
+                // The text printed through TextScreen does not identify
+                // the process that prints it.
+                self.message_speed.set(self.take_next_speed(None));
                 // Copy the text to the driver's buffer.
                 let printed_len = self.buffer.map_or(0, |buf| {
                     // Compute how many characters we can copy to the driver's buffer.
@@ -391,6 +1767,11 @@ impl<'a, L: Led, A: Alarm<'a>> TextScreen<'a> for LedMatrixText<'a, L, A> {
                     // we have copied to thed driver's buffer.
                     max_len
                 });
+                // The text printed through TextScreen has no owner.
+                self.text_owner.clear();
+                // The new text starts a new series of repeats and a new speed ramp.
+                self.passes.set(0);
+                self.characters_shown.set(0);
                 // Store the received buffer in a field so that we can
                 // return it to TextScreen from the deferred callback.
                 self.client_buffer.replace(buffer);
@@ -406,18 +1787,34 @@ impl<'a, L: Led, A: Alarm<'a>> TextScreen<'a> for LedMatrixText<'a, L, A> {
                 // not be automatically called. If the new length of the text
                 // is different from 0, we can immedialty print the next
                 // letter or digit.
-                if previous_len == 0 && printed_len != 0 {
+                if self.animation_owner.is_some() {
+                    // The text starts when the animation ends.
+                } else if self.has_attribute(ATTRIBUTE_FLASH)
+                    && self.is_enabled.get()
+                    && printed_len != 0
+                {
+                    // Light all the LEDs for half of the time before
+                    // displaying the next letter or digit.
+                    self.phase.set(Phase::Flash);
+                    self.render();
+                    self.alarm.set_alarm(
+                        self.alarm.now(),
+                        self.alarm.ticks_from_ms(self.current_speed() / 2),
+                    );
+                } else if previous_len == 0 && printed_len != 0 {
                     self.display_next();
                 }
                 Ok(())
             } else {
                 // Inform the TextScreen that it sent us an invalid length
                 // for the text it wants us to display.
+                self.increment(&self.statistics.dropped);
                 Err((ErrorCode::SIZE, buffer))
             }
         } else {
             // Inform the TextScreen that we have another action in progress
             // and that it should try again later.
+            self.increment(&self.statistics.dropped);
             Err((ErrorCode::BUSY, buffer))
         }
     }
@@ -491,8 +1888,12 @@ impl<'a, L: Led, A: Alarm<'a>> TextScreen<'a> for LedMatrixText<'a, L, A> {
             self.status.set(Status::ExecutesCommand);
             // Reset the position
             self.position.set(0);
+            self.backwards.set(false);
+            self.passes.set(0);
+            self.characters_shown.set(0);
             // Set the text's length to 0
             self.len.set(0);
+            self.text_owner.clear();
             // Clear what is currently displayed on the LED matrix
             self.clear();
             // Ask the kernel to send us a deferred callback (software interrupt)
@@ -509,20 +1910,22 @@ impl<'a, L: Led, A: Alarm<'a>> TextScreen<'a> for LedMatrixText<'a, L, A> {
 }
 
 /// This implementation allows `LedMatrixText` to expose a setup syscall API
-impl<'a, L: Led, A: Alarm<'a>> SyscallDriver for LedMatrixText<'a, L, A> {
-    fn allocate_grant(&self, _: ProcessId) -> Result<(), Error> {
-        // there is no grant used by this driver, we just ignore
-        // the function call and return success
-        Ok(())
+impl<'a, L: Led, A: Alarm<'a>, F: Font5x5> SyscallDriver for LedMatrixText<'a, L, A, F> {
+    fn allocate_grant(&self, process_id: ProcessId) -> Result<(), Error> {
+        // The kernel asked us to allocate the grant, all we have to
+        // do is to try to enter it. The kernel will do the task of
+        // allocating it for us.
+        self.grant.enter(process_id, |_, _| {})
     }
 
     fn command(
         &self,
         command_number: usize,
         r2: usize,
-        _r3: usize,
-        _process_id: ProcessId,
+        r3: usize,
+        process_id: ProcessId,
     ) -> CommandReturn {
+        self.release_exited();
         match command_number {
             // Tock's convention states that all syscall drivers must return *success* or *success_...* for
             // command number 0. This allows processes to verify if a driver is present.
@@ -532,9 +1935,197 @@ impl<'a, L: Led, A: Alarm<'a>> SyscallDriver for LedMatrixText<'a, L, A> {
                 self.speed.set(r2 as u32);
                 CommandReturn::success()
             }
+            // Set the attributes of the next printed message to the flags stored in *r2*.
+            //  bit 0 - blink, bit 1 - inverse video, bit 2 - flash once
+            2 => {
+                if r2 & !ATTRIBUTES == 0 {
+                    self.next_attributes.set(r2);
+                    CommandReturn::success()
+                } else {
+                    CommandReturn::failure(ErrorCode::INVAL)
+                }
+            }
+            // Set the display mode to the value stored in *r2*.
+            //  0 - continuous, 1 - one word at a time, 2 - Morse code, 3 - smooth scroll,
+            //  4 - smooth scroll up, 5 - smooth scroll down, 6 - wipe, 7 - dissolve
+            3 => match r2 {
+                0 => {
+                    self.mode.set(Mode::Continuous);
+                    CommandReturn::success()
+                }
+                1 => {
+                    self.mode.set(Mode::Words);
+                    CommandReturn::success()
+                }
+                2 => {
+                    self.mode.set(Mode::Morse);
+                    self.morse.set(None);
+                    CommandReturn::success()
+                }
+                3 => {
+                    self.mode.set(Mode::Scroll);
+                    self.scroll_step.set(SCROLL_STEPS);
+                    CommandReturn::success()
+                }
+                4 => {
+                    self.mode.set(Mode::ScrollUp);
+                    self.scroll_step.set(SCROLL_STEPS);
+                    CommandReturn::success()
+                }
+                5 => {
+                    self.mode.set(Mode::ScrollDown);
+                    self.scroll_step.set(SCROLL_STEPS);
+                    CommandReturn::success()
+                }
+                6 => {
+                    self.mode.set(Mode::Wipe);
+                    self.scroll_step.set(SCROLL_STEPS);
+                    CommandReturn::success()
+                }
+                7 => {
+                    self.mode.set(Mode::Dissolve);
+                    self.scroll_step.set(SCROLL_STEPS);
+                    CommandReturn::success()
+                }
+                _ => CommandReturn::failure(ErrorCode::INVAL),
+            },
+            // Get the statistic selected by *r2*.
+            //  0 - frames rendered, 1 - messages completed, 2 - messages overwritten,
+            //  3 - messages dropped, 4 - queue depth, 5 - average callback latency (us)
+            4 => match self.statistic(r2) {
+                Some(value) => CommandReturn::success_u32(value),
+                None => CommandReturn::failure(ErrorCode::INVAL),
+            },
+            // Reset the statistics
+            5 => {
+                self.reset_statistics();
+                CommandReturn::success()
+            }
+            // Display the value stored in *r2* in hexadecimal.
+            6 => self.print_hex(process_id, r2 as u32).into(),
+            // Set the glyph displayed for unknown characters to the bits stored in *r2*.
+            //  bit 24 - upper left LED, bit 0 - lower right LED
+            7 => {
+                self.set_placeholder(r2 as u32);
+                CommandReturn::success()
+            }
+            // Display the signed fixed point value stored in *r2* with *r3* decimals.
+            8 => self.print_fixed_point(process_id, r2 as i32, r3).into(),
+            // Set the direction of the text
+            //  0 - left to right, 1 - reversed (right to left)
+            9 => match r2 {
+                0 | 1 => {
+                    self.reversed.set(r2 == 1);
+                    CommandReturn::success()
+                }
+                _ => CommandReturn::failure(ErrorCode::INVAL),
+            },
+            // Set the number of times the text is displayed to *r2*, 0 means forever.
+            // When the last repeat ends, the driver schedules upcall 0.
+            10 => {
+                self.repeat.set(r2);
+                self.passes.set(0);
+                CommandReturn::success()
+            }
+            // Pause the display, the displayed character stays on the matrix. The
+            // display resumes if the process exits.
+            11 => self.pause(process_id).into(),
+            // Resume the display from the character where it was paused, only the
+            // process that paused the display can resume it.
+            12 => self.resume(process_id).into(),
+            // Set the time in ms the matrix is blank between two characters to *r2*.
+            13 => {
+                self.gap.set(r2 as u32);
+                CommandReturn::success()
+            }
+            // Set the number of times a blinking character blinks to *r2*, at most
+            // `MAX_BLINK_RATE` times and at most once per ms of the character's time.
+            14 => match r2 {
+                0 => CommandReturn::failure(ErrorCode::INVAL),
+                rate if rate > MAX_BLINK_RATE || rate as u32 > self.character_time() => {
+                    CommandReturn::failure(ErrorCode::INVAL)
+                }
+                _ => {
+                    self.blink_rate.set(r2 as u32);
+                    CommandReturn::success()
+                }
+            },
+            // Enable (r2 = 1) or disable (r2 = 0) the bounce mode, the text is displayed
+            // to its end and then back to its start.
+            15 => match r2 {
+                0 | 1 => {
+                    self.bounce.set(r2 == 1);
+                    self.backwards.set(false);
+                    CommandReturn::success()
+                }
+                _ => CommandReturn::failure(ErrorCode::INVAL),
+            },
+            // Play the frames allowed by the process, upcall 1 is scheduled when
+            // the last frame has been played.
+            16 => self.play_animation(process_id).into(),
+            // Set the speed at which the speed ramp starts to *r2*, 0 disables the ramp.
+            17 => {
+                self.ramp_start.set(r2 as u32);
+                CommandReturn::success()
+            }
+            // Set the speed at which the speed ramp ends to *r2*, 0 disables the ramp.
+            18 => {
+                self.ramp_target.set(r2 as u32);
+                CommandReturn::success()
+            }
+            // Set the time in ms of the pause between two words in word mode to *r2*,
+            // 0 restores the default pause.
+            19 => {
+                self.word_pause.set(r2 as u32);
+                CommandReturn::success()
+            }
+            // Set the speed of the next message printed by this process to *r2*,
+            // the following messages use the display speed again. Until the
+            // message is printed, only this process can change the speed.
+            20 => self.set_next_speed(process_id, r2 as u32).into(),
             _ => CommandReturn::failure(ErrorCode::NOSUPPORT),
         }
     }
 
-    /* the default implementation of the *allow_...* functions is used */
+    fn allow_readonly(
+        &self,
+        process_id: ProcessId,
+        allow_num: usize,
+        mut buffer: ReadOnlyProcessBuffer,
+    ) -> Result<ReadOnlyProcessBuffer, (ReadOnlyProcessBuffer, ErrorCode)> {
+        match allow_num {
+            // The glyphs registered by the process, `USER_GLYPH_SIZE` bytes each
+            0 => {
+                let res = self.grant.enter(process_id, |app, _| {
+                    // Store the new buffer and return the previous one.
+                    mem::swap(&mut app.glyphs, &mut buffer);
+                    app.glyphs.len()
+                });
+                match res {
+                    Ok(len) => {
+                        if len > 0 {
+                            // The last process that registers glyphs owns them.
+                            self.glyphs_owner.set(process_id);
+                        } else if self.glyphs_owner.contains(&process_id) {
+                            self.glyphs_owner.clear();
+                        }
+                        Ok(buffer)
+                    }
+                    Err(err) => Err((buffer, err.into())),
+                }
+            }
+            // The frames of the process' animation, `FRAME_SIZE` bytes each
+            1 => {
+                let res = self.grant.enter(process_id, |app, _| {
+                    // Store the new buffer and return the previous one.
+                    mem::swap(&mut app.frames, &mut buffer);
+                });
+                match res {
+                    Ok(()) => Ok(buffer),
+                    Err(err) => Err((buffer, err.into())),
+                }
+            }
+            _ => Err((buffer, ErrorCode::NOSUPPORT)),
+        }
+    }
 }
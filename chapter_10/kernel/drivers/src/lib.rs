@@ -3,3 +3,40 @@
 
 /// The driver that offers the text screen service.
 pub mod led_matrix_text;
+
+/// The fonts used by the text screen driver.
+pub mod font;
+
+/// The slots of the processes in the kernel's processes array.
+pub mod process_slot;
+
+/// The driver that allows the supervisor process to enable
+/// and disable other drivers.
+pub mod driver_toggle;
+
+/// The per process fault policy with delayed restarts.
+pub mod fault_policy;
+
+/// The driver that restarts processes that stop sending heartbeats.
+pub mod heartbeat;
+
+/// The pool of static buffers used by the split-phase drivers.
+pub mod buffer_pool;
+
+/// The driver that maps driver errors to short codes.
+pub mod error_messages;
+
+/// The driver that describes the board to the processes.
+pub mod board_info;
+
+/// The registry that detects pins claimed by more than one driver.
+pub mod pin_registry;
+
+/// The driver that plays on and off patterns on GPIO pins.
+pub mod gpio_pattern;
+
+/// The 64 bit monotonic time that does not wrap around.
+pub mod time64;
+
+/// The driver that plays a tone for every displayed character.
+pub mod character_tones;
@@ -0,0 +1,83 @@
+use core::cell::Cell;
+
+/// A pin claimed by a driver
+#[derive(Copy, Clone)]
+struct PinClaim {
+    /// The pin's number
+    pin: usize,
+    /// The driver that uses the pin
+    claimant: &'static str,
+    /// What the driver uses the pin for
+    function: &'static str,
+    /// The pin is shared on purpose with the other shared claims
+    shared: bool,
+}
+
+/// The registry of the pins used by the board's drivers
+///
+/// The board claims every pin it hands to a driver while it initializes
+/// the drivers. A pin that is claimed twice stops the boot with an error
+/// that lists both drivers, instead of letting them silently misbehave.
+/// Drivers that are wired to the same pin on purpose use shared claims.
+/// The registry stores at most `N` claims.
+pub struct PinRegistry<const N: usize> {
+    /// The pins claimed so far
+    claims: [Cell<Option<PinClaim>>; N],
+}
+
+impl<const N: usize> PinRegistry<N> {
+    /// Initializes a new empty registry
+    pub fn new() -> Self {
+        PinRegistry {
+            claims: [(); N].map(|_| Cell::new(None)),
+        }
+    }
+
+    /// Claims a pin for a driver
+    ///
+    /// Panics if the pin is already claimed or if the registry is full.
+    pub fn claim(&self, pin: usize, claimant: &'static str, function: &'static str) {
+        self.add(pin, claimant, function, false);
+    }
+
+    /// Claims a pin that a driver shares with other drivers
+    ///
+    /// The pin can be claimed by any number of shared claims, for instance
+    /// an LED that the board wires to two drivers. Panics if the pin has
+    /// an exclusive claim or if the registry is full.
+    pub fn claim_shared(&self, pin: usize, claimant: &'static str, function: &'static str) {
+        self.add(pin, claimant, function, true);
+    }
+
+    /// Stores a claim
+    fn add(&self, pin: usize, claimant: &'static str, function: &'static str, shared: bool) {
+        for slot in self.claims.iter() {
+            match slot.get() {
+                Some(claim) if claim.pin == pin && !(claim.shared && shared) => panic!(
+                    "pin {} is claimed by {} ({}) and by {} ({})",
+                    pin, claim.claimant, claim.function, claimant, function
+                ),
+                Some(_) => {}
+                None => {
+                    slot.set(Some(PinClaim {
+                        pin,
+                        claimant,
+                        function,
+                        shared,
+                    }));
+                    return;
+                }
+            }
+        }
+        panic!(
+            "the pin registry is full, cannot claim pin {} for {} ({})",
+            pin, claimant, function
+        );
+    }
+}
+
+impl<const N: usize> Default for PinRegistry<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
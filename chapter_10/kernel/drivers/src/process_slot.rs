@@ -0,0 +1,19 @@
+use kernel::process::Process;
+
+/// The kernel's processes array, as given by the board to `Kernel::new`
+/// and `load_processes`
+pub type Processes = [Option<&'static dyn Process>];
+
+/// Returns the slot of a process in the kernel's processes array
+///
+/// The kernel loads the processes in the order in which they are
+/// stored in flash, so the slot is decided by whoever flashes the
+/// board. Unlike the process name, it is not something that the
+/// process declares about itself.
+pub fn slot_of(processes: &Processes, process: &dyn Process) -> Option<usize> {
+    processes.iter().position(|slot| {
+        slot.map_or(false, |loaded| {
+            loaded as *const dyn Process as *const () == process as *const dyn Process as *const ()
+        })
+    })
+}
@@ -0,0 +1,136 @@
+use core::cell::Cell;
+use kernel::hil::time::{Alarm, AlarmClient, Frequency, Ticks};
+use kernel::process::{Error, ProcessId};
+use kernel::syscall::{CommandReturn, SyscallDriver};
+use kernel::ErrorCode;
+
+/// The driver number
+///
+/// As this is not one of Tock's standard drivers,
+/// its number has to be higher or equal to 0xa0000.
+///
+/// Our previous driver was 0xa0008 so we use the
+/// number available.
+pub const DRIVER_NUM: usize = 0xa0009;
+
+/// Structure representing the 64 bit monotonic time
+///
+/// The alarm's counter is 32 bits wide (or less) and wraps around after
+/// a while. `Time64` counts the wrap arounds, checking the counter at
+/// least twice per wrap around, and offers a 64 bit time that does not
+/// wrap around during the lifetime of the board.
+pub struct Time64<'a, A: Alarm<'a>> {
+    /// The alarm that provides the counter and the checkpoints
+    alarm: &'a A,
+
+    /// The number of times the counter has wrapped around
+    overflows: Cell<u32>,
+
+    /// The value of the counter at the last reading
+    last: Cell<u32>,
+}
+
+impl<'a, A: Alarm<'a>> Time64<'a, A> {
+    /// Initializes a new structure
+    pub fn new(alarm: &'a A) -> Self {
+        Time64 {
+            alarm,
+            overflows: Cell::new(0),
+            last: Cell::new(0),
+        }
+    }
+
+    /// Starts the periodic checkpoint
+    ///
+    /// The board has to call this after setting `Time64` as the
+    /// alarm's client.
+    pub fn start(&self) {
+        self.now_ticks();
+        self.set_checkpoint();
+    }
+
+    /// Sets the alarm for the next checkpoint, half a wrap around from now
+    fn set_checkpoint(&self) {
+        self.alarm
+            .set_alarm(self.alarm.now(), A::Ticks::half_max_value());
+    }
+
+    /// Returns the number of ticks since the board started
+    pub fn now_ticks(&self) -> u64 {
+        let now = self.alarm.now().into_u32();
+        // The counter is smaller than at the last reading, so it has wrapped around.
+        if now < self.last.get() {
+            self.overflows.set(self.overflows.get() + 1);
+        }
+        self.last.set(now);
+        ((self.overflows.get() as u64) << A::Ticks::width()) | now as u64
+    }
+
+    /// Returns the number of microseconds since the board started
+    pub fn now_us(&self) -> u64 {
+        let ticks = self.now_ticks();
+        let frequency = A::Frequency::frequency() as u64;
+        // Convert the whole seconds and the remainder separately,
+        // so that the multiplication does not overflow.
+        (ticks / frequency) * 1_000_000 + (ticks % frequency) * 1_000_000 / frequency
+    }
+
+    /// Converts a number of milliseconds to ticks
+    pub fn ticks_from_ms(&self, ms: u32) -> u64 {
+        ms as u64 * A::Frequency::frequency() as u64 / 1000
+    }
+
+    /// Returns the interval for an alarm that expires at `deadline`
+    ///
+    /// Alarms cannot be set for more than half a wrap around of the
+    /// counter, so for a deadline that is further away the interval is
+    /// shorter and the client has to set the alarm again when it expires.
+    pub fn alarm_interval(&self, deadline: u64) -> A::Ticks {
+        let left = deadline.saturating_sub(self.now_ticks());
+        let half = A::Ticks::half_max_value();
+        if left < half.into_u32() as u64 {
+            A::Ticks::from(left as u32)
+        } else {
+            half
+        }
+    }
+}
+
+/// This implementation allows `Time64` to use an alarm.
+impl<'a, A: Alarm<'a>> AlarmClient for Time64<'a, A> {
+    /// Called at every checkpoint
+    fn alarm(&self) {
+        // Reading the time records the wrap around of the counter.
+        self.now_ticks();
+        self.set_checkpoint();
+    }
+}
+
+/// The implementation of `SyscallDriver` makes `Time64` a syscall driver
+impl<'a, A: Alarm<'a>> SyscallDriver for Time64<'a, A> {
+    fn allocate_grant(&self, _: ProcessId) -> Result<(), Error> {
+        // there is no grant used by this driver, we just ignore
+        // the function call and return success
+        Ok(())
+    }
+
+    fn command(
+        &self,
+        command_number: usize,
+        _r2: usize,
+        _r3: usize,
+        _process_id: ProcessId,
+    ) -> CommandReturn {
+        match command_number {
+            // Tock's convention states that all syscall drivers must return *success* or *success_...* for
+            // command number 0. This allows processes to verify if a driver is present.
+            0 => CommandReturn::success(),
+            // Return the number of microseconds since the board started
+            1 => CommandReturn::success_u64(self.now_us()),
+            // Inform the process that we do not understand the command
+            _ => CommandReturn::failure(ErrorCode::NOSUPPORT),
+        }
+    }
+
+    /* the default implementation of the *allow_...* functions is used */
+}
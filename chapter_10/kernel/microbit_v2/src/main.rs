@@ -41,12 +41,24 @@ const GPIO_P8: Pin = Pin::P0_10;
 const GPIO_P9: Pin = Pin::P0_09;
 const GPIO_P16: Pin = Pin::P1_02;
 
+// P13, P14 and P15 are used by the GpioPattern driver
+const GPIO_P13: Pin = Pin::P0_17;
+const GPIO_P14: Pin = Pin::P0_01;
+const GPIO_P15: Pin = Pin::P0_13;
+
 const UART_TX_PIN: Pin = Pin::P0_06;
 const UART_RX_PIN: Pin = Pin::P1_08;
 
 /// LED matrix
 const LED_MATRIX_COLS: [Pin; 5] = [Pin::P0_28, Pin::P0_11, Pin::P0_31, Pin::P1_05, Pin::P0_30];
 const LED_MATRIX_ROWS: [Pin; 5] = [Pin::P0_21, Pin::P0_22, Pin::P0_15, Pin::P0_24, Pin::P0_19];
+/// The LEDs of the matrix that are known to be dead, bit 24 is the top left LED
+const LED_MATRIX_DEAD_LEDS: u32 = 0;
+/// The LED reserved as the secure indicator that only the kernel can light, the top right
+/// LED. It is lit while the display shows a kernel message, such as an error code.
+const LED_MATRIX_SECURE_INDICATOR: Option<usize> = Some(4);
+/// The glyph displayed for the characters that the font does not have
+const LED_MATRIX_PLACEHOLDER: u32 = drivers::font!("#####", "#...#", "#...#", "#...#", "#####");
 
 // Speaker
 
@@ -59,9 +71,40 @@ const I2C_SCL_PIN: Pin = Pin::P0_08;
 /// UART Writer for panic!()s.
 pub mod io;
 
+/// The slot of the supervisor process in the processes array. The kernel loads
+/// the applications in the order in which they are flashed, so the supervisor
+/// has to be the first application in flash.
+const SUPERVISOR_SLOT: usize = 0;
+
 // State for loading and holding applications.
 // How should the kernel respond when a process faults.
-const FAULT_RESPONSE: kernel::process::PanicFaultPolicy = kernel::process::PanicFaultPolicy {};
+//
+// Security critical processes are stopped at the first fault, all the
+// other processes are restarted at most 3 times, first after 1 second
+// and then doubling the delay after each restart.
+const FAULT_POLICIES: [(usize, drivers::fault_policy::RestartPolicy); 1] =
+    [(SUPERVISOR_SLOT, drivers::fault_policy::RestartPolicy::Stop)];
+const DEFAULT_FAULT_POLICY: drivers::fault_policy::RestartPolicy =
+    drivers::fault_policy::RestartPolicy::Restart {
+        max_restarts: 3,
+        backoff_ms: 1000,
+    };
+
+/// The capability that allows the fault policy manager to restart processes
+struct FaultPolicyCapability;
+unsafe impl capabilities::ProcessManagementCapability for FaultPolicyCapability {}
+
+// Which processes have to send heartbeats.
+//
+// The supervisor has to send a heartbeat at least every 5 seconds, it is
+// restarted at most 2 times if it misses one and stopped afterwards. These
+// restarts do not use up the restarts of the fault policy.
+const HEARTBEAT_SUPERVISED: [drivers::heartbeat::Supervision; 1] =
+    [drivers::heartbeat::Supervision {
+        slot: SUPERVISOR_SLOT,
+        timeout_ms: 5000,
+        max_restarts: 2,
+    }];
 
 // Number of concurrent processes this platform supports.
 const NUM_PROCS: usize = 4;
@@ -79,6 +122,38 @@ pub static mut STACK_MEMORY: [u8; 0x1000] = [0; 0x1000];
 // debug mode requires more stack space
 // pub static mut STACK_MEMORY: [u8; 0x2000] = [0; 0x2000];
 
+/// The drivers that the board registers with the kernel
+///
+/// `with_driver` only looks up these drivers and `BoardInfo`
+/// reports them to the processes.
+const DRIVERS: [usize; 24] = [
+    capsules::console::DRIVER_NUM,
+    capsules::gpio::DRIVER_NUM,
+    capsules::alarm::DRIVER_NUM,
+    capsules::button::DRIVER_NUM,
+    // The LED matrix driver is not registered, it could light the
+    // secure indicator (LED_MATRIX_SECURE_INDICATOR).
+    capsules::ninedof::DRIVER_NUM,
+    capsules::adc::DRIVER_NUM,
+    capsules::temperature::DRIVER_NUM,
+    capsules::lsm303agr::DRIVER_NUM,
+    capsules::rng::DRIVER_NUM,
+    capsules::ble_advertising_driver::DRIVER_NUM,
+    capsules::buzzer_driver::DRIVER_NUM,
+    capsules::app_flash_driver::DRIVER_NUM,
+    capsules::sound_pressure::DRIVER_NUM,
+    capsules::text_screen::DRIVER_NUM,
+    drivers::led_matrix_text::DRIVER_NUM,
+    drivers::driver_toggle::DRIVER_NUM,
+    drivers::heartbeat::DRIVER_NUM,
+    drivers::error_messages::DRIVER_NUM,
+    drivers::board_info::DRIVER_NUM,
+    drivers::gpio_pattern::DRIVER_NUM,
+    drivers::time64::DRIVER_NUM,
+    drivers::character_tones::DRIVER_NUM,
+    kernel::ipc::DRIVER_NUM,
+];
+
 /// Supported drivers by the platform
 pub struct MicroBit {
     ble_radio: &'static capsules::ble_advertising_driver::BLE<
@@ -126,6 +201,42 @@ pub struct MicroBit {
         >,
         capsules::virtual_alarm::VirtualMuxAlarm<'static, nrf52833::rtc::Rtc<'static>>,
     >,
+    /// Add the `DriverToggle` driver to the board implementation structure.
+    driver_toggle: &'static drivers::driver_toggle::DriverToggle<'static>,
+    /// Add the `Heartbeat` driver to the board implementation structure.
+    heartbeat: &'static drivers::heartbeat::Heartbeat<
+        'static,
+        capsules::virtual_alarm::VirtualMuxAlarm<'static, nrf52833::rtc::Rtc<'static>>,
+    >,
+    /// Add the `ErrorMessages` driver to the board implementation structure.
+    error_messages: &'static drivers::error_messages::ErrorMessages<
+        'static,
+        LedMatrixLed<
+            'static,
+            nrf52::gpio::GPIOPin<'static>,
+            capsules::virtual_alarm::VirtualMuxAlarm<'static, nrf52::rtc::Rtc<'static>>,
+        >,
+        capsules::virtual_alarm::VirtualMuxAlarm<'static, nrf52833::rtc::Rtc<'static>>,
+    >,
+    /// Add the `BoardInfo` driver to the board implementation structure.
+    board_info: &'static drivers::board_info::BoardInfo<'static>,
+    /// Add the `GpioPattern` driver to the board implementation structure.
+    gpio_pattern: &'static drivers::gpio_pattern::GpioPattern<
+        'static,
+        nrf52::gpio::GPIOPin<'static>,
+        capsules::virtual_alarm::VirtualMuxAlarm<'static, nrf52833::rtc::Rtc<'static>>,
+    >,
+    /// Add the `Time64` driver to the board implementation structure.
+    time64: &'static drivers::time64::Time64<
+        'static,
+        capsules::virtual_alarm::VirtualMuxAlarm<'static, nrf52833::rtc::Rtc<'static>>,
+    >,
+    /// Add the `CharacterTones` driver to the board implementation structure.
+    character_tones: &'static drivers::character_tones::CharacterTones<
+        'static,
+        capsules::virtual_pwm::PwmPinUser<'static, nrf52833::pwm::Pwm>,
+        capsules::virtual_alarm::VirtualMuxAlarm<'static, nrf52833::rtc::Rtc<'static>>,
+    >,
 }
 
 impl SyscallDriverLookup for MicroBit {
@@ -133,12 +244,21 @@ impl SyscallDriverLookup for MicroBit {
     where
         F: FnOnce(Option<&dyn kernel::syscall::SyscallDriver>) -> R,
     {
+        // The drivers that are not in the list are not registered.
+        if !DRIVERS.contains(&driver_num) {
+            return f(None);
+        }
         match driver_num {
             capsules::console::DRIVER_NUM => f(Some(self.console)),
             capsules::gpio::DRIVER_NUM => f(Some(self.gpio)),
             capsules::alarm::DRIVER_NUM => f(Some(self.alarm)),
             capsules::button::DRIVER_NUM => f(Some(self.button)),
-            capsules::led_matrix::DRIVER_NUM => f(Some(self.led)),
+            // The LED matrix driver can light any LED, including the secure
+            // indicator, so the processes cannot use it once the indicator is reserved.
+            capsules::led_matrix::DRIVER_NUM => match LED_MATRIX_SECURE_INDICATOR {
+                Some(_) => f(None),
+                None => f(Some(self.led)),
+            },
             capsules::ninedof::DRIVER_NUM => f(Some(self.ninedof)),
             capsules::adc::DRIVER_NUM => f(Some(self.adc)),
             capsules::temperature::DRIVER_NUM => f(Some(self.temperature)),
@@ -152,6 +272,20 @@ impl SyscallDriverLookup for MicroBit {
             capsules::text_screen::DRIVER_NUM => f(Some(self.text_screen)),
             // Register the `LedMatrixText` driver with the kernel.
             drivers::led_matrix_text::DRIVER_NUM => f(Some(self.led_matrix_text)),
+            // Register the `DriverToggle` driver with the kernel.
+            drivers::driver_toggle::DRIVER_NUM => f(Some(self.driver_toggle)),
+            // Register the `Heartbeat` driver with the kernel.
+            drivers::heartbeat::DRIVER_NUM => f(Some(self.heartbeat)),
+            // Register the `ErrorMessages` driver with the kernel.
+            drivers::error_messages::DRIVER_NUM => f(Some(self.error_messages)),
+            // Register the `BoardInfo` driver with the kernel.
+            drivers::board_info::DRIVER_NUM => f(Some(self.board_info)),
+            // Register the `GpioPattern` driver with the kernel.
+            drivers::gpio_pattern::DRIVER_NUM => f(Some(self.gpio_pattern)),
+            // Register the `Time64` driver with the kernel.
+            drivers::time64::DRIVER_NUM => f(Some(self.time64)),
+            // Register the `CharacterTones` driver with the kernel.
+            drivers::character_tones::DRIVER_NUM => f(Some(self.character_tones)),
             kernel::ipc::DRIVER_NUM => f(Some(&self.ipc)),
             _ => f(None),
         }
@@ -162,7 +296,8 @@ impl KernelResources<nrf52833::chip::NRF52<'static, Nrf52833DefaultPeripherals<'
     for MicroBit
 {
     type SyscallDriverLookup = Self;
-    type SyscallFilter = ();
+    // Use the `DriverToggle` driver to filter the system calls.
+    type SyscallFilter = drivers::driver_toggle::DriverToggle<'static>;
     type ProcessFault = ();
     type Scheduler = RoundRobinSched<'static>;
     type SchedulerTimer = cortexm4::systick::SysTick;
@@ -172,7 +307,7 @@ impl KernelResources<nrf52833::chip::NRF52<'static, Nrf52833DefaultPeripherals<'
         &self
     }
     fn syscall_filter(&self) -> &Self::SyscallFilter {
-        &()
+        self.driver_toggle
     }
     fn process_fault(&self) -> &Self::ProcessFault {
         &()
@@ -240,6 +375,48 @@ pub unsafe fn main() {
         None,
     );
 
+    //--------------------------------------------------------------------------
+    // PIN REGISTRY
+    //--------------------------------------------------------------------------
+
+    // Claim the pins used by the drivers. If two drivers use the same pin,
+    // for instance a GPIO pin that is also an LED matrix column, the
+    // board stops with an error that lists both drivers.
+    let pin_registry = static_init!(
+        drivers::pin_registry::PinRegistry<32>,
+        drivers::pin_registry::PinRegistry::new()
+    );
+    // The microphone LED is also the kernel's debug LED.
+    pin_registry.claim_shared(LED_KERNEL_PIN as usize, "debug", "kernel LED");
+    pin_registry.claim_shared(
+        LED_MICROPHONE_PIN as usize,
+        "sound_pressure",
+        "microphone LED",
+    );
+    pin_registry.claim(_GPIO_P0 as usize, "adc", "analog input 0");
+    pin_registry.claim(_GPIO_P1 as usize, "adc", "analog input 1");
+    pin_registry.claim(_GPIO_P2 as usize, "adc", "analog input 2");
+    pin_registry.claim(GPIO_P8 as usize, "gpio", "GPIO 8");
+    pin_registry.claim(GPIO_P9 as usize, "gpio", "GPIO 9");
+    pin_registry.claim(GPIO_P16 as usize, "gpio", "GPIO 16");
+    pin_registry.claim(GPIO_P13 as usize, "gpio_pattern", "pattern pin 0");
+    pin_registry.claim(GPIO_P14 as usize, "gpio_pattern", "pattern pin 1");
+    pin_registry.claim(GPIO_P15 as usize, "gpio_pattern", "pattern pin 2");
+    pin_registry.claim(BUTTON_A as usize, "button", "button A");
+    pin_registry.claim(BUTTON_B as usize, "button", "button B");
+    pin_registry.claim(TOUCH_LOGO as usize, "button", "touch logo");
+    pin_registry.claim(UART_TX_PIN as usize, "console", "UART TX");
+    pin_registry.claim(UART_RX_PIN as usize, "console", "UART RX");
+    for pin in LED_MATRIX_COLS.iter() {
+        pin_registry.claim(*pin as usize, "led_matrix", "LED matrix column");
+    }
+    for pin in LED_MATRIX_ROWS.iter() {
+        pin_registry.claim(*pin as usize, "led_matrix", "LED matrix row");
+    }
+    pin_registry.claim(SPEAKER_PIN as usize, "buzzer", "speaker");
+    pin_registry.claim(I2C_SDA_PIN as usize, "lsm303agr", "I2C SDA");
+    pin_registry.claim(I2C_SCL_PIN as usize, "lsm303agr", "I2C SCL");
+
     //--------------------------------------------------------------------------
     // GPIO
     //--------------------------------------------------------------------------
@@ -315,6 +492,58 @@ pub unsafe fn main() {
     )
     .finalize(components::alarm_component_helper!(nrf52::rtc::Rtc));
 
+    // Initialize a virtual alarm for the Time64 driver
+    let virtual_alarm_time64 = static_init!(
+        capsules::virtual_alarm::VirtualMuxAlarm<'static, nrf52833::rtc::Rtc<'static>>,
+        capsules::virtual_alarm::VirtualMuxAlarm::new(mux_alarm)
+    );
+
+    // Initialize the Time64 driver
+    let time64 = static_init!(
+        drivers::time64::Time64<
+            'static,
+            capsules::virtual_alarm::VirtualMuxAlarm<'static, nrf52833::rtc::Rtc<'static>>,
+        >,
+        drivers::time64::Time64::new(virtual_alarm_time64)
+    );
+
+    // Set the driver as the alarm's client and start
+    // counting the wrap arounds of the alarm's counter.
+    virtual_alarm_time64.set_alarm_client(time64);
+    time64.start();
+
+    //--------------------------------------------------------------------------
+    // FAULT POLICY
+    //--------------------------------------------------------------------------
+
+    // Initialize a virtual alarm for the fault policy manager
+    let virtual_alarm_fault_policy = static_init!(
+        capsules::virtual_alarm::VirtualMuxAlarm<'static, nrf52833::rtc::Rtc>,
+        capsules::virtual_alarm::VirtualMuxAlarm::new(mux_alarm)
+    );
+
+    // Initialize the fault policy manager that decides what happens
+    // to a process that faults.
+    let fault_policy = static_init!(
+        drivers::fault_policy::FaultPolicyManager<
+            'static,
+            capsules::virtual_alarm::VirtualMuxAlarm<'static, nrf52833::rtc::Rtc>,
+            FaultPolicyCapability,
+        >,
+        drivers::fault_policy::FaultPolicyManager::new(
+            board_kernel,
+            virtual_alarm_fault_policy,
+            time64,
+            &PROCESSES,
+            &FAULT_POLICIES,
+            DEFAULT_FAULT_POLICY,
+            FaultPolicyCapability
+        )
+    );
+
+    // Set the manager as the alarm's client.
+    virtual_alarm_fault_policy.set_alarm_client(fault_policy);
+
     //--------------------------------------------------------------------------
     // PWM & BUZZER
     //--------------------------------------------------------------------------
@@ -600,8 +829,27 @@ pub unsafe fn main() {
         capsules::virtual_alarm::VirtualMuxAlarm::new(mux_alarm)
     );
 
-    // Initialize a 'static buffer of 50 for the LedMatrixText driver
-    let led_matrix_buffer = static_init!([u8; 50], [0; 50]);
+    // Initialize a pool with the 'static buffer for the text
+    // that processes print using the LedMatrixText driver
+    let led_matrix_text_buffers = static_init!(
+        drivers::led_matrix_text::TextBufferPool,
+        drivers::led_matrix_text::TextBufferPool::new(static_init!(
+            [[u8; drivers::led_matrix_text::TEXT_LEN]; 1],
+            [[0; drivers::led_matrix_text::TEXT_LEN]; 1]
+        ))
+    );
+
+    // Initialize a pool of 'static buffers for the messages that
+    // kernel capsules print using the LedMatrixText driver
+    let led_matrix_kernel_buffers = static_init!(
+        drivers::led_matrix_text::KernelBufferPool,
+        drivers::led_matrix_text::KernelBufferPool::new(static_init!(
+            [[u8; drivers::led_matrix_text::KERNEL_MESSAGE_LEN];
+                drivers::led_matrix_text::KERNEL_MESSAGES],
+            [[0; drivers::led_matrix_text::KERNEL_MESSAGE_LEN];
+                drivers::led_matrix_text::KERNEL_MESSAGES]
+        ))
+    );
 
     // Initialize the LedMatrixText using the static_init! macro
     // This returns a 'static reference to the newly created LedMatrixText structure
@@ -655,12 +903,19 @@ pub unsafe fn main() {
                 (4, 4)
             ),
             virtual_alarm_led_matrix_text,
-            // Send the allocated buffer to the driver
-            led_matrix_buffer,
+            // Send the pool with the buffer for the text to the driver
+            led_matrix_text_buffers,
+            // Send the pool of buffers for kernel messages to the driver
+            led_matrix_kernel_buffers,
             // Set the default speed in ms
             300,
             // Set the kernel's deferred caller
-            dynamic_deferred_caller
+            dynamic_deferred_caller,
+            // Create the grant that stores the glyphs registered by the processes
+            board_kernel.create_grant(
+                drivers::led_matrix_text::DRIVER_NUM,
+                &memory_allocation_capability
+            )
         ),
     );
 
@@ -677,6 +932,20 @@ pub unsafe fn main() {
             .expect("no deferred call slot available for led matrix text"),
     );
 
+    // Avoid the LEDs that are known to be dead.
+    led_matrix_text.set_dead_leds(LED_MATRIX_DEAD_LEDS);
+
+    // Set the glyph displayed for unknown characters.
+    led_matrix_text.set_placeholder(LED_MATRIX_PLACEHOLDER);
+
+    // Reserve the secure indicator, the processes cannot light it.
+    if let Some(led) = LED_MATRIX_SECURE_INDICATOR {
+        led_matrix_text.set_secure_indicator(led);
+    }
+
+    // Display the accented letters as their base letters.
+    led_matrix_text.set_transliteration(&drivers::font::LATIN1_TRANSLITERATION);
+
     // Initialize a new TextScreen driver...
     let text_screen = components::text_screen::TextScreenComponent::new(
         board_kernel,
@@ -686,6 +955,176 @@ pub unsafe fn main() {
     // ... with a buffer of length 50.
     .finalize(components::screen_buffer_size!(50));
 
+    // Initialize the DriverToggle driver with the list of drivers
+    // that the supervisor process is allowed to disable.
+    let driver_toggle = static_init!(
+        drivers::driver_toggle::DriverToggle<'static>,
+        drivers::driver_toggle::DriverToggle::new(
+            &[
+                capsules::gpio::DRIVER_NUM,
+                capsules::button::DRIVER_NUM,
+                capsules::led_matrix::DRIVER_NUM,
+                capsules::ninedof::DRIVER_NUM,
+                capsules::adc::DRIVER_NUM,
+                capsules::temperature::DRIVER_NUM,
+                capsules::lsm303agr::DRIVER_NUM,
+                capsules::rng::DRIVER_NUM,
+                capsules::ble_advertising_driver::DRIVER_NUM,
+                capsules::buzzer_driver::DRIVER_NUM,
+                capsules::app_flash_driver::DRIVER_NUM,
+                capsules::sound_pressure::DRIVER_NUM,
+                capsules::text_screen::DRIVER_NUM,
+                drivers::led_matrix_text::DRIVER_NUM,
+            ],
+            // The processes and the slot of the supervisor process
+            &PROCESSES,
+            SUPERVISOR_SLOT,
+        )
+    );
+
+    // Initialize a virtual alarm for the Heartbeat driver
+    let virtual_alarm_heartbeat = static_init!(
+        capsules::virtual_alarm::VirtualMuxAlarm<'static, nrf52833::rtc::Rtc>,
+        capsules::virtual_alarm::VirtualMuxAlarm::new(mux_alarm)
+    );
+
+    // Initialize the Heartbeat driver
+    let heartbeat = static_init!(
+        drivers::heartbeat::Heartbeat<
+            'static,
+            capsules::virtual_alarm::VirtualMuxAlarm<'static, nrf52833::rtc::Rtc>,
+        >,
+        drivers::heartbeat::Heartbeat::new(
+            virtual_alarm_heartbeat,
+            time64,
+            &PROCESSES,
+            &HEARTBEAT_SUPERVISED
+        )
+    );
+
+    // Set the driver as the alarm's client.
+    virtual_alarm_heartbeat.set_alarm_client(heartbeat);
+
+    // Initialize the ErrorMessages driver, it displays the
+    // short codes using the LedMatrixText driver
+    let error_messages = static_init!(
+        drivers::error_messages::ErrorMessages<
+            'static,
+            LedMatrixLed<
+                'static,
+                nrf52::gpio::GPIOPin<'static>,
+                capsules::virtual_alarm::VirtualMuxAlarm<'static, nrf52::rtc::Rtc<'static>>,
+            >,
+            capsules::virtual_alarm::VirtualMuxAlarm<'static, nrf52833::rtc::Rtc<'static>>,
+        >,
+        drivers::error_messages::ErrorMessages::new(
+            led_matrix_text,
+            &drivers::error_messages::ERROR_MESSAGES
+        )
+    );
+
+    // Display the missed heartbeats on the LED matrix.
+    heartbeat.set_error_reporter(error_messages);
+
+    // Describe the board to the processes
+    let board_descriptor = static_init!(
+        drivers::board_info::BoardDescriptor<'static>,
+        drivers::board_info::BoardDescriptor {
+            name: "BBC micro:bit v2",
+            chip: "nRF52833",
+            // The drivers registered by the board
+            drivers: &DRIVERS,
+            // The LED matrix has 5 columns and 5 rows
+            led_matrix: (5, 5),
+            // The drivers that read the board's sensors
+            sensors: &[
+                capsules::ninedof::DRIVER_NUM,
+                capsules::temperature::DRIVER_NUM,
+                capsules::lsm303agr::DRIVER_NUM,
+                capsules::sound_pressure::DRIVER_NUM,
+            ],
+        }
+    );
+
+    // Initialize the BoardInfo driver
+    let board_info = static_init!(
+        drivers::board_info::BoardInfo<'static>,
+        drivers::board_info::BoardInfo::new(
+            board_descriptor,
+            board_kernel.create_grant(
+                drivers::board_info::DRIVER_NUM,
+                &memory_allocation_capability
+            )
+        )
+    );
+
+    // Initialize a virtual alarm for the GpioPattern driver
+    let virtual_alarm_gpio_pattern = static_init!(
+        capsules::virtual_alarm::VirtualMuxAlarm<'static, nrf52833::rtc::Rtc<'static>>,
+        capsules::virtual_alarm::VirtualMuxAlarm::new(mux_alarm)
+    );
+
+    // Initialize the GpioPattern driver with the pins
+    // that can play on and off patterns
+    let gpio_pattern = static_init!(
+        drivers::gpio_pattern::GpioPattern<
+            'static,
+            nrf52::gpio::GPIOPin<'static>,
+            capsules::virtual_alarm::VirtualMuxAlarm<'static, nrf52833::rtc::Rtc<'static>>,
+        >,
+        drivers::gpio_pattern::GpioPattern::new(
+            static_init!(
+                [&'static nrf52::gpio::GPIOPin<'static>; 3],
+                [
+                    &nrf52833_peripherals.gpio_port[GPIO_P13],
+                    &nrf52833_peripherals.gpio_port[GPIO_P14],
+                    &nrf52833_peripherals.gpio_port[GPIO_P15],
+                ]
+            ),
+            virtual_alarm_gpio_pattern,
+            time64
+        )
+    );
+
+    // Set the driver as the alarm's client.
+    virtual_alarm_gpio_pattern.set_alarm_client(gpio_pattern);
+
+    // Initialize a virtual PWM pin for the CharacterTones driver, it shares
+    // the speaker with the buzzer driver through the PWM mux
+    let virtual_pwm_character_tones = static_init!(
+        capsules::virtual_pwm::PwmPinUser<'static, nrf52833::pwm::Pwm>,
+        capsules::virtual_pwm::PwmPinUser::new(
+            mux_pwm,
+            nrf52833::pinmux::Pinmux::new(SPEAKER_PIN as u32)
+        )
+    );
+    virtual_pwm_character_tones.add_to_mux();
+
+    // Initialize a virtual alarm for the CharacterTones driver
+    let virtual_alarm_character_tones = static_init!(
+        capsules::virtual_alarm::VirtualMuxAlarm<'static, nrf52833::rtc::Rtc<'static>>,
+        capsules::virtual_alarm::VirtualMuxAlarm::new(mux_alarm)
+    );
+
+    // Initialize the CharacterTones driver
+    let character_tones = static_init!(
+        drivers::character_tones::CharacterTones<
+            'static,
+            capsules::virtual_pwm::PwmPinUser<'static, nrf52833::pwm::Pwm>,
+            capsules::virtual_alarm::VirtualMuxAlarm<'static, nrf52833::rtc::Rtc<'static>>,
+        >,
+        drivers::character_tones::CharacterTones::new(
+            virtual_pwm_character_tones,
+            virtual_alarm_character_tones
+        )
+    );
+
+    // Set the driver as the alarm's client.
+    virtual_alarm_character_tones.set_alarm_client(character_tones);
+
+    // Notify the driver of every character displayed by the LED matrix.
+    led_matrix_text.set_character_client(character_tones);
+
     let scheduler = components::sched::round_robin::RoundRobinComponent::new(&PROCESSES)
         .finalize(components::rr_component_helper!(NUM_PROCS));
 
@@ -717,6 +1156,20 @@ pub unsafe fn main() {
         text_screen,
         // Add the LedMatrixText driver to the boards implementation initialization.
         led_matrix_text,
+        // Add the DriverToggle driver to the boards implementation initialization.
+        driver_toggle,
+        // Add the Heartbeat driver to the boards implementation initialization.
+        heartbeat,
+        // Add the ErrorMessages driver to the boards implementation initialization.
+        error_messages,
+        // Add the BoardInfo driver to the boards implementation initialization.
+        board_info,
+        // Add the GpioPattern driver to the boards implementation initialization.
+        gpio_pattern,
+        // Add the Time64 driver to the boards implementation initialization.
+        time64,
+        // Add the CharacterTones driver to the boards implementation initialization.
+        character_tones,
     };
 
     let chip = static_init!(
@@ -755,7 +1208,7 @@ pub unsafe fn main() {
             &_eappmem as *const u8 as usize - &_sappmem as *const u8 as usize,
         ),
         &mut PROCESSES,
-        &FAULT_RESPONSE,
+        fault_policy,
         &process_management_capability,
     )
     .unwrap_or_else(|err| {
@@ -763,5 +1216,8 @@ pub unsafe fn main() {
         debug!("{:?}", err);
     });
 
+    // Start the heartbeat deadlines once the processes are loaded.
+    heartbeat.start();
+
     board_kernel.kernel_loop(&microbit, chip, Some(&microbit.ipc), &main_loop_capability);
 }
@@ -51,9 +51,58 @@ pub static mut STACK_MEMORY: [u8; 0x1000] = [0; 0x1000];
 #[link_section = ".flash_bootloader"]
 static FLASH_BOOTLOADER: [u8; 256] = flash_bootloader::FLASH_BOOTLOADER;
 
+/// The LEDs of the matrix that are known to be dead, bit 24 is the top left LED
+const LED_MATRIX_DEAD_LEDS: u32 = 0;
+/// The LED reserved as the secure indicator that only the kernel can light, the top right
+/// LED. It is lit while the display shows a kernel message, such as an error code.
+const LED_MATRIX_SECURE_INDICATOR: Option<usize> = Some(4);
+/// The glyph displayed for the characters that the font does not have
+const LED_MATRIX_PLACEHOLDER: u32 = drivers::font!("#####", "#...#", "#...#", "#...#", "#####");
+
+/// The pins used by the GpioPattern driver, pattern pins 0 to 2. All the free
+/// pins are used by the GPIO driver, so the `gpio_pattern` feature moves GPIO 20
+/// to 22 from the GPIO driver to the GpioPattern driver.
+#[cfg(feature = "gpio_pattern")]
+const GPIO_PATTERN_PINS: [RPGpio; 3] = [RPGpio::GPIO20, RPGpio::GPIO21, RPGpio::GPIO22];
+/// Without the `gpio_pattern` feature, GPIO 20 to 22 stay with the GPIO
+/// driver and the GpioPattern driver has no pins.
+#[cfg(not(feature = "gpio_pattern"))]
+const GPIO_PATTERN_PINS: [RPGpio; 0] = [];
+
+/// The slot of the supervisor process in the processes array. The kernel loads
+/// the applications in the order in which they are flashed, so the supervisor
+/// has to be the first application in flash.
+const SUPERVISOR_SLOT: usize = 0;
+
 // State for loading and holding applications.
 // How should the kernel respond when a process faults.
-const FAULT_RESPONSE: kernel::process::PanicFaultPolicy = kernel::process::PanicFaultPolicy {};
+//
+// Security critical processes are stopped at the first fault, all the
+// other processes are restarted at most 3 times, first after 1 second
+// and then doubling the delay after each restart.
+const FAULT_POLICIES: [(usize, drivers::fault_policy::RestartPolicy); 1] =
+    [(SUPERVISOR_SLOT, drivers::fault_policy::RestartPolicy::Stop)];
+const DEFAULT_FAULT_POLICY: drivers::fault_policy::RestartPolicy =
+    drivers::fault_policy::RestartPolicy::Restart {
+        max_restarts: 3,
+        backoff_ms: 1000,
+    };
+
+/// The capability that allows the fault policy manager to restart processes
+struct FaultPolicyCapability;
+unsafe impl capabilities::ProcessManagementCapability for FaultPolicyCapability {}
+
+// Which processes have to send heartbeats.
+//
+// The supervisor has to send a heartbeat at least every 5 seconds, it is
+// restarted at most 2 times if it misses one and stopped afterwards. These
+// restarts do not use up the restarts of the fault policy.
+const HEARTBEAT_SUPERVISED: [drivers::heartbeat::Supervision; 1] =
+    [drivers::heartbeat::Supervision {
+        slot: SUPERVISOR_SLOT,
+        timeout_ms: 5000,
+        max_restarts: 2,
+    }];
 
 // Number of concurrent processes this platform supports.
 const NUM_PROCS: usize = 4;
@@ -64,6 +113,28 @@ static mut PROCESSES: [Option<&'static dyn kernel::process::Process>; NUM_PROCS]
 
 static mut CHIP: Option<&'static Rp2040<Rp2040DefaultPeripherals>> = None;
 /* ... */
+/// The drivers that the board registers with the kernel
+///
+/// `with_driver` only looks up these drivers and `BoardInfo`
+/// reports them to the processes.
+const DRIVERS: [usize; 15] = [
+    capsules::console::DRIVER_NUM,
+    capsules::alarm::DRIVER_NUM,
+    capsules::gpio::DRIVER_NUM,
+    capsules::led::DRIVER_NUM,
+    kernel::ipc::DRIVER_NUM,
+    capsules::adc::DRIVER_NUM,
+    capsules::temperature::DRIVER_NUM,
+    capsules::text_screen::DRIVER_NUM,
+    drivers::led_matrix_text::DRIVER_NUM,
+    drivers::driver_toggle::DRIVER_NUM,
+    drivers::heartbeat::DRIVER_NUM,
+    drivers::error_messages::DRIVER_NUM,
+    drivers::board_info::DRIVER_NUM,
+    drivers::gpio_pattern::DRIVER_NUM,
+    drivers::time64::DRIVER_NUM,
+];
+
 /// Supported drivers by the platform
 pub struct RaspberryPiPico {
     ipc: kernel::ipc::IPC<NUM_PROCS, NUM_UPCALLS_IPC>,
@@ -90,6 +161,36 @@ pub struct RaspberryPiPico {
         >,
         capsules::virtual_alarm::VirtualMuxAlarm<'static, RPTimer<'static>>,
     >,
+    /// Add the `DriverToggle` driver to the board implementation structure.
+    driver_toggle: &'static drivers::driver_toggle::DriverToggle<'static>,
+    /// Add the `Heartbeat` driver to the board implementation structure.
+    heartbeat: &'static drivers::heartbeat::Heartbeat<
+        'static,
+        capsules::virtual_alarm::VirtualMuxAlarm<'static, RPTimer<'static>>,
+    >,
+    /// Add the `ErrorMessages` driver to the board implementation structure.
+    error_messages: &'static drivers::error_messages::ErrorMessages<
+        'static,
+        LedMatrixLed<
+            'static,
+            RPGpioPin<'static>,
+            capsules::virtual_alarm::VirtualMuxAlarm<'static, RPTimer<'static>>,
+        >,
+        capsules::virtual_alarm::VirtualMuxAlarm<'static, RPTimer<'static>>,
+    >,
+    /// Add the `BoardInfo` driver to the board implementation structure.
+    board_info: &'static drivers::board_info::BoardInfo<'static>,
+    /// Add the `GpioPattern` driver to the board implementation structure.
+    gpio_pattern: &'static drivers::gpio_pattern::GpioPattern<
+        'static,
+        RPGpioPin<'static>,
+        capsules::virtual_alarm::VirtualMuxAlarm<'static, RPTimer<'static>>,
+    >,
+    /// Add the `Time64` driver to the board implementation structure.
+    time64: &'static drivers::time64::Time64<
+        'static,
+        capsules::virtual_alarm::VirtualMuxAlarm<'static, RPTimer<'static>>,
+    >,
 }
 
 impl SyscallDriverLookup for RaspberryPiPico {
@@ -97,6 +198,10 @@ impl SyscallDriverLookup for RaspberryPiPico {
     where
         F: FnOnce(Option<&dyn SyscallDriver>) -> R,
     {
+        // The drivers that are not in the list are not registered.
+        if !DRIVERS.contains(&driver_num) {
+            return f(None);
+        }
         match driver_num {
             capsules::console::DRIVER_NUM => f(Some(self.console)),
             capsules::alarm::DRIVER_NUM => f(Some(self.alarm)),
@@ -109,6 +214,18 @@ impl SyscallDriverLookup for RaspberryPiPico {
             capsules::text_screen::DRIVER_NUM => f(Some(self.text_screen)),
             // Register the `LedMatrixText` driver with the kernel.
             drivers::led_matrix_text::DRIVER_NUM => f(Some(self.led_matrix_text)),
+            // Register the `DriverToggle` driver with the kernel.
+            drivers::driver_toggle::DRIVER_NUM => f(Some(self.driver_toggle)),
+            // Register the `Heartbeat` driver with the kernel.
+            drivers::heartbeat::DRIVER_NUM => f(Some(self.heartbeat)),
+            // Register the `ErrorMessages` driver with the kernel.
+            drivers::error_messages::DRIVER_NUM => f(Some(self.error_messages)),
+            // Register the `BoardInfo` driver with the kernel.
+            drivers::board_info::DRIVER_NUM => f(Some(self.board_info)),
+            // Register the `GpioPattern` driver with the kernel.
+            drivers::gpio_pattern::DRIVER_NUM => f(Some(self.gpio_pattern)),
+            // Register the `Time64` driver with the kernel.
+            drivers::time64::DRIVER_NUM => f(Some(self.time64)),
             _ => f(None),
         }
     }
@@ -116,7 +233,8 @@ impl SyscallDriverLookup for RaspberryPiPico {
 
 impl KernelResources<Rp2040<'static, Rp2040DefaultPeripherals<'static>>> for RaspberryPiPico {
     type SyscallDriverLookup = Self;
-    type SyscallFilter = ();
+    // Use the `DriverToggle` driver to filter the system calls.
+    type SyscallFilter = drivers::driver_toggle::DriverToggle<'static>;
     type ProcessFault = ();
     type Scheduler = RoundRobinSched<'static>;
     type SchedulerTimer = cortexm0p::systick::SysTick;
@@ -126,7 +244,7 @@ impl KernelResources<Rp2040<'static, Rp2040DefaultPeripherals<'static>>> for Ras
         &self
     }
     fn syscall_filter(&self) -> &Self::SyscallFilter {
-        &()
+        self.driver_toggle
     }
     fn process_fault(&self) -> &Self::ProcessFault {
         &()
@@ -329,6 +447,56 @@ pub unsafe fn main() {
     )
     .finalize(components::alarm_component_helper!(RPTimer));
 
+    // Initialize a virtual alarm for the Time64 driver
+    let virtual_alarm_time64 = static_init!(
+        capsules::virtual_alarm::VirtualMuxAlarm<'static, RPTimer<'static>>,
+        capsules::virtual_alarm::VirtualMuxAlarm::new(mux_alarm)
+    );
+
+    // Initialize the Time64 driver
+    let time64 = static_init!(
+        drivers::time64::Time64<
+            'static,
+            capsules::virtual_alarm::VirtualMuxAlarm<'static, RPTimer<'static>>,
+        >,
+        drivers::time64::Time64::new(virtual_alarm_time64)
+    );
+
+    // Set the driver as the alarm's client and start
+    // counting the wrap arounds of the alarm's counter.
+    virtual_alarm_time64.set_alarm_client(time64);
+    time64.start();
+
+    // FAULT POLICY
+
+    // Initialize a virtual alarm for the fault policy manager
+    let virtual_alarm_fault_policy = static_init!(
+        capsules::virtual_alarm::VirtualMuxAlarm<'static, RPTimer<'static>>,
+        capsules::virtual_alarm::VirtualMuxAlarm::new(mux_alarm)
+    );
+
+    // Initialize the fault policy manager that decides what happens
+    // to a process that faults.
+    let fault_policy = static_init!(
+        drivers::fault_policy::FaultPolicyManager<
+            'static,
+            capsules::virtual_alarm::VirtualMuxAlarm<'static, RPTimer<'static>>,
+            FaultPolicyCapability,
+        >,
+        drivers::fault_policy::FaultPolicyManager::new(
+            board_kernel,
+            virtual_alarm_fault_policy,
+            time64,
+            &PROCESSES,
+            &FAULT_POLICIES,
+            DEFAULT_FAULT_POLICY,
+            FaultPolicyCapability
+        )
+    );
+
+    // Set the manager as the alarm's client.
+    virtual_alarm_fault_policy.set_alarm_client(fault_policy);
+
     // UART
     // Create a shared UART channel for kernel debug.
     let uart_mux = components::console::UartMuxComponent::new(
@@ -348,6 +516,7 @@ pub unsafe fn main() {
     // Create the debugger object that handles calls to `debug!()`.
     components::debug_writer::DebugWriterComponent::new(uart_mux).finalize(());
 
+    #[cfg(not(feature = "gpio_pattern"))]
     let gpio = GpioComponent::new(
         board_kernel,
         capsules::gpio::DRIVER_NUM,
@@ -395,6 +564,28 @@ pub unsafe fn main() {
     )
     .finalize(components::gpio_component_buf!(RPGpioPin<'static>));
 
+    // The same pins without GPIO 20 to 22, which are used by the
+    // GpioPattern driver (GPIO_PATTERN_PINS).
+    #[cfg(feature = "gpio_pattern")]
+    let gpio = GpioComponent::new(
+        board_kernel,
+        capsules::gpio::DRIVER_NUM,
+        components::gpio_component_helper!(
+            RPGpioPin,
+            12 => &peripherals.pins.get_pin(RPGpio::GPIO12),
+            13 => &peripherals.pins.get_pin(RPGpio::GPIO13),
+            14 => &peripherals.pins.get_pin(RPGpio::GPIO14),
+            15 => &peripherals.pins.get_pin(RPGpio::GPIO15),
+            16 => &peripherals.pins.get_pin(RPGpio::GPIO16),
+            17 => &peripherals.pins.get_pin(RPGpio::GPIO17),
+            18 => &peripherals.pins.get_pin(RPGpio::GPIO18),
+            19 => &peripherals.pins.get_pin(RPGpio::GPIO19),
+            23 => &peripherals.pins.get_pin(RPGpio::GPIO23),
+            24 => &peripherals.pins.get_pin(RPGpio::GPIO24)
+        ),
+    )
+    .finalize(components::gpio_component_buf!(RPGpioPin<'static>));
+
     let led = LedsComponent::new(components::led_component_helper!(
         LedHigh<'static, RPGpioPin<'static>>,
         LedHigh::new(&peripherals.pins.get_pin(RPGpio::GPIO25))
@@ -478,8 +669,27 @@ pub unsafe fn main() {
         capsules::virtual_alarm::VirtualMuxAlarm::new(mux_alarm)
     );
 
-    // Initialize a 'static buffer of 50 for the LedMatrixText driver
-    let led_matrix_buffer = static_init!([u8; 50], [0; 50]);
+    // Initialize a pool with the 'static buffer for the text
+    // that processes print using the LedMatrixText driver
+    let led_matrix_text_buffers = static_init!(
+        drivers::led_matrix_text::TextBufferPool,
+        drivers::led_matrix_text::TextBufferPool::new(static_init!(
+            [[u8; drivers::led_matrix_text::TEXT_LEN]; 1],
+            [[0; drivers::led_matrix_text::TEXT_LEN]; 1]
+        ))
+    );
+
+    // Initialize a pool of 'static buffers for the messages that
+    // kernel capsules print using the LedMatrixText driver
+    let led_matrix_kernel_buffers = static_init!(
+        drivers::led_matrix_text::KernelBufferPool,
+        drivers::led_matrix_text::KernelBufferPool::new(static_init!(
+            [[u8; drivers::led_matrix_text::KERNEL_MESSAGE_LEN];
+                drivers::led_matrix_text::KERNEL_MESSAGES],
+            [[0; drivers::led_matrix_text::KERNEL_MESSAGE_LEN];
+                drivers::led_matrix_text::KERNEL_MESSAGES]
+        ))
+    );
 
     // Initialize the LedMatrixText using the static_init! macro
     // This returns a 'static reference to the newly created LedMatrixText structure
@@ -533,12 +743,19 @@ pub unsafe fn main() {
                 (4, 4)
             ),
             virtual_alarm_led_matrix_text,
-            // Send the allocated buffer to the driver
-            led_matrix_buffer,
+            // Send the pool with the buffer for the text to the driver
+            led_matrix_text_buffers,
+            // Send the pool of buffers for kernel messages to the driver
+            led_matrix_kernel_buffers,
             // Set the default speed in ms
             300,
             // Set the kernel's deferred caller
-            dynamic_deferred_caller
+            dynamic_deferred_caller,
+            // Create the grant that stores the glyphs registered by the processes
+            board_kernel.create_grant(
+                drivers::led_matrix_text::DRIVER_NUM,
+                &memory_allocation_capability
+            )
         )
     );
 
@@ -555,6 +772,20 @@ pub unsafe fn main() {
             .expect("no deferred call slot available for led matrix text"),
     );
 
+    // Avoid the LEDs that are known to be dead.
+    led_matrix_text.set_dead_leds(LED_MATRIX_DEAD_LEDS);
+
+    // Set the glyph displayed for unknown characters.
+    led_matrix_text.set_placeholder(LED_MATRIX_PLACEHOLDER);
+
+    // Reserve the secure indicator, the processes cannot light it.
+    if let Some(led) = LED_MATRIX_SECURE_INDICATOR {
+        led_matrix_text.set_secure_indicator(led);
+    }
+
+    // Display the accented letters as their base letters.
+    led_matrix_text.set_transliteration(&drivers::font::LATIN1_TRANSLITERATION);
+
     // Initialize a new TextScreen driver...
     let text_screen = components::text_screen::TextScreenComponent::new(
         board_kernel,
@@ -564,6 +795,123 @@ pub unsafe fn main() {
     // ... with a buffer of length 50.
     .finalize(components::screen_buffer_size!(50));
 
+    // Initialize the DriverToggle driver with the list of drivers
+    // that the supervisor process is allowed to disable.
+    let driver_toggle = static_init!(
+        drivers::driver_toggle::DriverToggle<'static>,
+        drivers::driver_toggle::DriverToggle::new(
+            &[
+                capsules::gpio::DRIVER_NUM,
+                capsules::led::DRIVER_NUM,
+                capsules::adc::DRIVER_NUM,
+                capsules::temperature::DRIVER_NUM,
+                capsules::text_screen::DRIVER_NUM,
+                drivers::led_matrix_text::DRIVER_NUM,
+            ],
+            // The processes and the slot of the supervisor process
+            &PROCESSES,
+            SUPERVISOR_SLOT,
+        )
+    );
+
+    // Initialize a virtual alarm for the Heartbeat driver
+    let virtual_alarm_heartbeat = static_init!(
+        capsules::virtual_alarm::VirtualMuxAlarm<'static, RPTimer<'static>>,
+        capsules::virtual_alarm::VirtualMuxAlarm::new(mux_alarm)
+    );
+
+    // Initialize the Heartbeat driver
+    let heartbeat = static_init!(
+        drivers::heartbeat::Heartbeat<
+            'static,
+            capsules::virtual_alarm::VirtualMuxAlarm<'static, RPTimer<'static>>,
+        >,
+        drivers::heartbeat::Heartbeat::new(
+            virtual_alarm_heartbeat,
+            time64,
+            &PROCESSES,
+            &HEARTBEAT_SUPERVISED
+        )
+    );
+
+    // Set the driver as the alarm's client.
+    virtual_alarm_heartbeat.set_alarm_client(heartbeat);
+
+    // Initialize the ErrorMessages driver, it displays the
+    // short codes using the LedMatrixText driver
+    let error_messages = static_init!(
+        drivers::error_messages::ErrorMessages<
+            'static,
+            LedMatrixLed<
+                'static,
+                RPGpioPin<'static>,
+                capsules::virtual_alarm::VirtualMuxAlarm<'static, RPTimer<'static>>,
+            >,
+            capsules::virtual_alarm::VirtualMuxAlarm<'static, RPTimer<'static>>,
+        >,
+        drivers::error_messages::ErrorMessages::new(
+            led_matrix_text,
+            &drivers::error_messages::ERROR_MESSAGES
+        )
+    );
+
+    // Display the missed heartbeats on the LED matrix.
+    heartbeat.set_error_reporter(error_messages);
+
+    // Describe the board to the processes
+    let board_descriptor = static_init!(
+        drivers::board_info::BoardDescriptor<'static>,
+        drivers::board_info::BoardDescriptor {
+            name: "Raspberry Pi Pico",
+            chip: "RP2040",
+            // The drivers registered by the board
+            drivers: &DRIVERS,
+            // The LED matrix has 5 columns and 5 rows
+            led_matrix: (5, 5),
+            // The drivers that read the board's sensors
+            sensors: &[capsules::temperature::DRIVER_NUM],
+        }
+    );
+
+    // Initialize the BoardInfo driver
+    let board_info = static_init!(
+        drivers::board_info::BoardInfo<'static>,
+        drivers::board_info::BoardInfo::new(
+            board_descriptor,
+            board_kernel.create_grant(
+                drivers::board_info::DRIVER_NUM,
+                &memory_allocation_capability
+            )
+        )
+    );
+
+    // Initialize a virtual alarm for the GpioPattern driver
+    let virtual_alarm_gpio_pattern = static_init!(
+        capsules::virtual_alarm::VirtualMuxAlarm<'static, RPTimer<'static>>,
+        capsules::virtual_alarm::VirtualMuxAlarm::new(mux_alarm)
+    );
+
+    // Initialize the GpioPattern driver with the pins
+    // that can play on and off patterns
+    let gpio_pattern = static_init!(
+        drivers::gpio_pattern::GpioPattern<
+            'static,
+            RPGpioPin<'static>,
+            capsules::virtual_alarm::VirtualMuxAlarm<'static, RPTimer<'static>>,
+        >,
+        drivers::gpio_pattern::GpioPattern::new(
+            static_init!(
+                [&'static RPGpioPin<'static>; GPIO_PATTERN_PINS.len()],
+                GPIO_PATTERN_PINS.map(|pin| peripherals.pins.get_pin(pin))
+            ),
+            virtual_alarm_gpio_pattern,
+            time64
+        )
+    );
+
+    // Set the driver as the alarm's client.
+    virtual_alarm_gpio_pattern.set_alarm_client(gpio_pattern);
+
     // PROCESS CONSOLE
     let process_console =
         components::process_console::ProcessConsoleComponent::new(board_kernel, uart_mux)
@@ -593,6 +941,18 @@ pub unsafe fn main() {
         text_screen,
         // Add the LedMatrixText driver to the boards implementation initialization.
         led_matrix_text,
+        // Add the DriverToggle driver to the boards implementation initialization.
+        driver_toggle,
+        // Add the Heartbeat driver to the boards implementation initialization.
+        heartbeat,
+        // Add the ErrorMessages driver to the boards implementation initialization.
+        error_messages,
+        // Add the BoardInfo driver to the boards implementation initialization.
+        board_info,
+        // Add the GpioPattern driver to the boards implementation initialization.
+        gpio_pattern,
+        // Add the Time64 driver to the boards implementation initialization.
+        time64,
     };
 
     let platform_type = match peripherals.sysinfo.get_platform() {
@@ -631,7 +991,7 @@ pub unsafe fn main() {
             &_eappmem as *const u8 as usize - &_sappmem as *const u8 as usize,
         ),
         &mut PROCESSES,
-        &FAULT_RESPONSE,
+        fault_policy,
         &process_management_capability,
     )
     .unwrap_or_else(|err| {
@@ -639,6 +999,9 @@ pub unsafe fn main() {
         debug!("{:?}", err);
     });
 
+    // Start the heartbeat deadlines once the processes are loaded.
+    heartbeat.start();
+
     board_kernel.kernel_loop(
         &raspberry_pi_pico,
         chip,